@@ -1,2 +1,5 @@
 // Count implementation
 pub trait Count: From<i64> {}
+
+impl Count for i64 {}
+impl Count for Option<i64> {}
@@ -1,7 +1,84 @@
-use sqlx::Database;
+use crate::dialect::{self, Dialect};
+use crate::value::Value;
 
 // select query implementation
 pub trait DeleteBuilder {
-    // returns query
-    fn to_sql<DB: Database>(&mut self) -> crate::Result<(String, Vec<()>)>;
+    // columns returned by `RETURNING` clause (all columns when not set)
+    const RETURNING: Option<&'static str> = None;
+
+    // returns query with `?` placeholders, rows are limited by `#[buildix(limit)]` field
+    fn to_raw_sql<DB: Dialect>(&mut self) -> crate::Result<(String, Vec<Value>)> {
+        let limit = self.get_limit();
+        self.to_raw_limited_sql::<DB>(limit)
+    }
+
+    // returns query limited to given number of rows instead (used by batches)
+    fn to_raw_limited_sql<DB: Dialect>(&mut self, limit: Option<u64>) -> crate::Result<(String, Vec<Value>)>;
+
+    // get_limit returns value of `#[buildix(limit)]` field (if available)
+    fn get_limit(&self) -> Option<u64> {
+        None
+    }
+
+    // returns query with placeholders of database
+    fn to_sql<DB: Dialect>(&mut self) -> crate::Result<(String, Vec<Value>)> {
+        let (query, values) = self.to_raw_sql::<DB>()?;
+        Ok((dialect::number::<DB>(&query), values))
+    }
+
+    // returns query with placeholders of database, which returns deleted rows by `RETURNING`
+    // clause (error when database does not support it)
+    fn to_returning_sql<DB: Dialect>(&mut self) -> crate::Result<(String, Vec<Value>)> {
+        if !DB::RETURNING {
+            return Err(crate::Error::Unsupported("RETURNING", DB::NAME));
+        }
+        let (query, values) = self.to_raw_sql::<DB>()?;
+        let query = format!("{} RETURNING {}", query, Self::RETURNING.unwrap_or("*"));
+        Ok((dialect::number::<DB>(&query), values))
+    }
+
+    // set_count sets number of deleted rows to `#[buildix(count)]` field (if available)
+    fn set_count(&mut self, _count: u64) {}
+}
+
+// delete_query returns DELETE query for given table, filter clause and limit
+pub fn delete_query<DB: Dialect>(table: &str, clause: Option<&str>, limit: Option<u64>) -> String {
+    match (clause, limit) {
+        (clause, Some(limit)) => DB::delete_limit(table, clause, limit),
+        (Some(clause), None) => format!("DELETE FROM {} WHERE {}", table, clause),
+        (None, None) => format!("DELETE FROM {}", table),
+    }
+}
+
+// soft_delete_check returns condition of rows which are not soft deleted. Flag column is boolean
+// (rendered by dialect), otherwise column is timestamp of deletion.
+pub fn soft_delete_check<DB: Dialect>(column: &str, flag: bool) -> String {
+    if flag {
+        format!("{} = {}", column, DB::boolean(false))
+    } else {
+        DB::null_check(column, true)
+    }
+}
+
+// soft_delete_query returns UPDATE query which marks rows as deleted by setting column to current
+// timestamp (or true, when column is flag). Rows which are already deleted are kept as they are.
+pub fn soft_delete_query<DB: Dialect>(
+    table: &str,
+    column: &str,
+    flag: bool,
+    clause: Option<&str>,
+    limit: Option<u64>,
+) -> String {
+    let set = if flag {
+        format!("{} = {}", column, DB::boolean(true))
+    } else {
+        format!("{} = {}", column, DB::now())
+    };
+    let mut clauses = vec![soft_delete_check::<DB>(column, flag)];
+    clauses.extend(clause.map(str::to_string));
+    let clause = crate::filter::and(&clauses);
+    match limit {
+        Some(limit) => DB::update_limit(table, &set, Some(&clause), limit),
+        None => format!("UPDATE {} SET {} WHERE {}", table, set, clause),
+    }
 }
@@ -0,0 +1,230 @@
+use serde_json::{json, Map, Value as Json};
+
+use crate::filter::fields::{IsNull, Ops};
+use crate::sort::Sort;
+
+// Schema is type of parameter value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Schema {
+    Integer(&'static str),
+    String,
+    Boolean,
+    Array(Box<Schema>),
+    Enum(Vec<&'static str>),
+}
+
+impl Schema {
+    // to_json returns JSON schema of value
+    pub fn to_json(&self) -> Json {
+        match self {
+            Schema::Integer(format) => json!({"type": "integer", "format": format}),
+            Schema::String => json!({"type": "string"}),
+            Schema::Boolean => json!({"type": "boolean"}),
+            Schema::Array(items) => json!({"type": "array", "items": items.to_json()}),
+            Schema::Enum(values) => json!({"type": "string", "enum": values}),
+        }
+    }
+
+    // schema of value of operator suffix (`in` accepts comma separated list)
+    fn op(&self, op: &str) -> Schema {
+        match op {
+            "in" => match self {
+                Schema::Array(_) => self.clone(),
+                _ => Schema::Array(Box::new(self.clone())),
+            },
+            "contains" | "icontains" => Schema::String,
+            "isnull" => Schema::Boolean,
+            _ => match self {
+                Schema::Array(items) => *items.clone(),
+                _ => self.clone(),
+            },
+        }
+    }
+}
+
+// Param describes parameter of builder (filter field, sort, limit or offset)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: String,
+    pub schema: Schema,
+
+    // operator suffixes accepted along with name (`age__gt`)
+    pub ops: Vec<String>,
+
+    pub description: Option<String>,
+    pub minimum: Option<i64>,
+}
+
+impl Param {
+    pub fn new(name: String, schema: Schema) -> Self {
+        Self {
+            name,
+            schema,
+            ops: vec![],
+            description: None,
+            minimum: None,
+        }
+    }
+
+    // expand returns parameter itself along with parameters of its operators
+    pub fn expand(&self) -> Vec<(String, Schema)> {
+        let mut result = vec![(self.name.clone(), self.schema.clone())];
+        for op in &self.ops {
+            result.push((format!("{}__{}", self.name, op), self.schema.op(op)));
+        }
+        result
+    }
+
+    fn schema_json(&self, schema: &Schema) -> Json {
+        let mut result = schema.to_json();
+        if let (Some(minimum), Schema::Integer(_)) = (self.minimum, schema) {
+            result["minimum"] = json!(minimum);
+        }
+        result
+    }
+}
+
+// supported_ops returns operators listed by field which are supported by its type
+// (`FromQuery::OPS`), so description matches accepted query parameters
+pub fn supported_ops(supported: &[&str], ops: &[&str]) -> Vec<String> {
+    ops.iter()
+        .filter(|op| crate::query::has_op(supported, op))
+        .map(|op| op.to_string())
+        .collect()
+}
+
+// Describe is implemented by types of builder fields. Values have schema, nested filters (and
+// builders) have parameters instead. It is implemented by derives with `#[buildix(describe)]`.
+pub trait Describe {
+    // schema of value, None for nested filters
+    fn schema() -> Option<Schema> {
+        None
+    }
+
+    // params returns parameters of filter, names are prefixed (`inner.`)
+    fn params(_prefix: &str) -> Vec<Param> {
+        vec![]
+    }
+
+    // json_schema returns JSON schema of object with all parameters
+    fn json_schema() -> Json
+    where
+        Self: Sized,
+    {
+        json_schema(&Self::params(""))
+    }
+
+    // openapi returns list of OpenAPI query parameters
+    fn openapi() -> Json
+    where
+        Self: Sized,
+    {
+        openapi(&Self::params(""))
+    }
+}
+
+// json_schema returns JSON schema of object with parameters as properties
+pub fn json_schema(params: &[Param]) -> Json {
+    let mut properties = Map::new();
+    for param in params {
+        for (name, schema) in param.expand() {
+            let mut property = param.schema_json(&schema);
+            if let Some(description) = &param.description {
+                property["description"] = json!(description);
+            }
+            properties.insert(name, property);
+        }
+    }
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    })
+}
+
+// openapi returns OpenAPI query parameters, lists are comma separated
+pub fn openapi(params: &[Param]) -> Json {
+    let mut result = vec![];
+    for param in params {
+        for (name, schema) in param.expand() {
+            let mut parameter = json!({
+                "name": name,
+                "in": "query",
+                "required": false,
+                "schema": param.schema_json(&schema),
+            });
+            if let Some(description) = &param.description {
+                parameter["description"] = json!(description);
+            }
+            if let Schema::Array(_) = schema {
+                parameter["style"] = json!("form");
+                parameter["explode"] = json!(false);
+            }
+            result.push(parameter);
+        }
+    }
+    Json::Array(result)
+}
+
+macro_rules! describe_impl {
+    {$T:ty, $schema:expr} => {
+        impl Describe for $T {
+            fn schema() -> Option<Schema> {
+                Some($schema)
+            }
+        }
+    };
+}
+
+describe_impl!(i32, Schema::Integer("int32"));
+describe_impl!(i64, Schema::Integer("int64"));
+describe_impl!(String, Schema::String);
+describe_impl!(str, Schema::String);
+describe_impl!(bool, Schema::Boolean);
+describe_impl!(IsNull, Schema::Boolean);
+describe_impl!(Sort, Schema::Enum(vec!["asc", "desc"]));
+
+impl<T> Describe for &T
+where
+    T: Describe + ?Sized,
+{
+    fn schema() -> Option<Schema> {
+        T::schema()
+    }
+
+    fn params(prefix: &str) -> Vec<Param> {
+        T::params(prefix)
+    }
+}
+
+impl<T> Describe for Option<T>
+where
+    T: Describe,
+{
+    fn schema() -> Option<Schema> {
+        T::schema()
+    }
+
+    fn params(prefix: &str) -> Vec<Param> {
+        T::params(prefix)
+    }
+}
+
+impl<T> Describe for Vec<T>
+where
+    T: Describe,
+{
+    fn schema() -> Option<Schema> {
+        T::schema().map(|schema| Schema::Array(Box::new(schema)))
+    }
+}
+
+impl<T> Describe for Ops<T>
+where
+    T: Describe,
+{
+    fn schema() -> Option<Schema> {
+        T::schema()
+    }
+}
@@ -0,0 +1,326 @@
+// Dialect describes how sql is rendered for given database. Queries are composed with `?`
+// placeholders (so fragments of filters, common table expressions and compound queries can be
+// joined freely), and `number` converts them to placeholders of database at the end.
+//
+// Dialect is implemented for backend marker types of this module (used without sqlx, e.g. with
+// tokio-postgres or rusqlite) and for sqlx databases of enabled features. Other databases
+// (CockroachDB, ClickHouse, ...) can implement it on their own marker type:
+//
+//     struct ClickHouse;
+//
+//     impl Dialect for ClickHouse {
+//         const NAME: &'static str = "clickhouse";
+//
+//         fn quote(ident: &str) -> String {
+//             format!("`{}`", ident.replace('`', "``"))
+//         }
+//     }
+pub trait Dialect {
+    // name of database
+    const NAME: &'static str;
+
+    // whether DELETE (and UPDATE) supports RETURNING clause
+    const RETURNING: bool = false;
+
+    // whether SELECT supports DISTINCT ON (...)
+    const DISTINCT_ON: bool = false;
+
+    // placeholder returns placeholder of n-th value (starting at 1)
+    fn placeholder(_n: usize) -> String {
+        "?".to_string()
+    }
+
+    // quote returns quoted identifier
+    fn quote(ident: &str) -> String {
+        format!("\"{}\"", ident.replace('"', "\"\""))
+    }
+
+    // limit_offset returns clause limiting rows of select (offset is only used along with limit),
+    // ordered tells whether query has ORDER BY
+    fn limit_offset(limit: u64, offset: Option<u64>, _ordered: bool) -> String {
+        match offset {
+            Some(offset) => format!("LIMIT {} OFFSET {}", limit, offset),
+            None => format!("LIMIT {}", limit),
+        }
+    }
+
+    // compound_part returns query of single builder in compound query (UNION, ...), so its own
+    // ORDER BY and LIMIT are kept
+    fn compound_part(query: &str) -> String {
+        format!("({})", query)
+    }
+
+    // null_check returns check whether expression is NULL (or is not NULL)
+    fn null_check(expr: &str, null: bool) -> String {
+        if null {
+            format!("{} IS NULL", expr)
+        } else {
+            format!("{} IS NOT NULL", expr)
+        }
+    }
+
+    // boolean returns boolean literal
+    fn boolean(value: bool) -> &'static str {
+        if value {
+            "TRUE"
+        } else {
+            "FALSE"
+        }
+    }
+
+    // delete_limit returns DELETE query which removes at most `limit` rows matching clause
+    fn delete_limit(table: &str, clause: Option<&str>, limit: u64) -> String {
+        match clause {
+            Some(clause) => format!("DELETE FROM {} WHERE {} LIMIT {}", table, clause, limit),
+            None => format!("DELETE FROM {} LIMIT {}", table, limit),
+        }
+    }
+
+    // update_limit returns UPDATE query which changes at most `limit` rows matching clause
+    fn update_limit(table: &str, set: &str, clause: Option<&str>, limit: u64) -> String {
+        match clause {
+            Some(clause) => format!("UPDATE {} SET {} WHERE {} LIMIT {}", table, set, clause, limit),
+            None => format!("UPDATE {} SET {} LIMIT {}", table, set, limit),
+        }
+    }
+
+    // now returns current timestamp
+    fn now() -> &'static str {
+        "CURRENT_TIMESTAMP"
+    }
+}
+
+// number replaces `?` placeholders of query with placeholders of database. Placeholders in string
+// literals and quoted identifiers are kept as they are. Every other `?` is placeholder, including
+// postgres jsonb operators (`?`, `?|`, `?&`), expressions have to use their functions instead
+// (`jsonb_exists`, `jsonb_exists_any`, `jsonb_exists_all`).
+pub fn number<DB: Dialect>(query: &str) -> String {
+    if DB::placeholder(1) == "?" {
+        return query.to_string();
+    }
+
+    let mut result = String::with_capacity(query.len());
+    let mut quote: Option<char> = None;
+    let mut counter = 0;
+
+    for ch in query.chars() {
+        match quote {
+            // closing quote (escaped quotes are just two quoted parts)
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None if ch == '\'' || ch == '"' || ch == '`' => quote = Some(ch),
+            None if ch == '?' => {
+                counter += 1;
+                result.push_str(&DB::placeholder(counter));
+                continue;
+            }
+            None => {}
+        }
+        result.push(ch);
+    }
+
+    result
+}
+
+// backend marker types (queries are rendered without sqlx)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Postgres;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MySql;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sqlite;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Mssql;
+
+impl Dialect for Postgres {
+    const NAME: &'static str = "postgres";
+    const RETURNING: bool = true;
+    const DISTINCT_ON: bool = true;
+
+    fn placeholder(n: usize) -> String {
+        format!("${}", n)
+    }
+
+    // postgres does not support DELETE ... LIMIT, rows are limited by their physical location
+    fn delete_limit(table: &str, clause: Option<&str>, limit: u64) -> String {
+        match clause {
+            Some(clause) => format!(
+                "DELETE FROM {0} WHERE ctid IN (SELECT ctid FROM {0} WHERE {1} LIMIT {2})",
+                table, clause, limit
+            ),
+            None => format!(
+                "DELETE FROM {0} WHERE ctid IN (SELECT ctid FROM {0} LIMIT {1})",
+                table, limit
+            ),
+        }
+    }
+
+    fn update_limit(table: &str, set: &str, clause: Option<&str>, limit: u64) -> String {
+        match clause {
+            Some(clause) => format!(
+                "UPDATE {0} SET {1} WHERE ctid IN (SELECT ctid FROM {0} WHERE {2} LIMIT {3})",
+                table, set, clause, limit
+            ),
+            None => format!(
+                "UPDATE {0} SET {1} WHERE ctid IN (SELECT ctid FROM {0} LIMIT {2})",
+                table, set, limit
+            ),
+        }
+    }
+
+    fn now() -> &'static str {
+        "now()"
+    }
+}
+
+impl Dialect for MySql {
+    const NAME: &'static str = "mysql";
+
+    fn quote(ident: &str) -> String {
+        format!("`{}`", ident.replace('`', "``"))
+    }
+
+    fn now() -> &'static str {
+        "NOW()"
+    }
+}
+
+impl Dialect for Sqlite {
+    const NAME: &'static str = "sqlite";
+    const RETURNING: bool = true;
+
+    // sqlite does not allow parenthesized queries in compound query, they are selected from
+    fn compound_part(query: &str) -> String {
+        format!("SELECT * FROM ({})", query)
+    }
+
+    // sqlite supports DELETE ... LIMIT only when compiled with SQLITE_ENABLE_UPDATE_DELETE_LIMIT
+    fn delete_limit(table: &str, clause: Option<&str>, limit: u64) -> String {
+        match clause {
+            Some(clause) => format!(
+                "DELETE FROM {0} WHERE rowid IN (SELECT rowid FROM {0} WHERE {1} LIMIT {2})",
+                table, clause, limit
+            ),
+            None => format!(
+                "DELETE FROM {0} WHERE rowid IN (SELECT rowid FROM {0} LIMIT {1})",
+                table, limit
+            ),
+        }
+    }
+
+    fn update_limit(table: &str, set: &str, clause: Option<&str>, limit: u64) -> String {
+        match clause {
+            Some(clause) => format!(
+                "UPDATE {0} SET {1} WHERE rowid IN (SELECT rowid FROM {0} WHERE {2} LIMIT {3})",
+                table, set, clause, limit
+            ),
+            None => format!(
+                "UPDATE {0} SET {1} WHERE rowid IN (SELECT rowid FROM {0} LIMIT {2})",
+                table, set, limit
+            ),
+        }
+    }
+}
+
+impl Dialect for Mssql {
+    const NAME: &'static str = "mssql";
+
+    fn placeholder(n: usize) -> String {
+        format!("@p{}", n)
+    }
+
+    fn quote(ident: &str) -> String {
+        format!("[{}]", ident.replace(']', "]]"))
+    }
+
+    // OFFSET ... FETCH needs ORDER BY in query, unordered query gets arbitrary one
+    fn limit_offset(limit: u64, offset: Option<u64>, ordered: bool) -> String {
+        let clause = format!(
+            "OFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+            offset.unwrap_or(0),
+            limit
+        );
+        if ordered {
+            clause
+        } else {
+            format!("ORDER BY (SELECT NULL) {}", clause)
+        }
+    }
+
+    fn boolean(value: bool) -> &'static str {
+        if value {
+            "1"
+        } else {
+            "0"
+        }
+    }
+
+    fn delete_limit(table: &str, clause: Option<&str>, limit: u64) -> String {
+        match clause {
+            Some(clause) => format!("DELETE TOP ({}) FROM {} WHERE {}", limit, table, clause),
+            None => format!("DELETE TOP ({}) FROM {}", limit, table),
+        }
+    }
+
+    fn update_limit(table: &str, set: &str, clause: Option<&str>, limit: u64) -> String {
+        match clause {
+            Some(clause) => format!("UPDATE TOP ({}) {} SET {} WHERE {}", limit, table, set, clause),
+            None => format!("UPDATE TOP ({}) {} SET {}", limit, table, set),
+        }
+    }
+}
+
+// sqlx_dialect implements dialect of sqlx database by its backend marker type
+#[cfg(feature = "sqlx")]
+macro_rules! sqlx_dialect {
+    ($DB:ty, $Backend:ty) => {
+        impl Dialect for $DB {
+            const NAME: &'static str = <$Backend as Dialect>::NAME;
+            const RETURNING: bool = <$Backend as Dialect>::RETURNING;
+            const DISTINCT_ON: bool = <$Backend as Dialect>::DISTINCT_ON;
+
+            fn placeholder(n: usize) -> String {
+                <$Backend as Dialect>::placeholder(n)
+            }
+            fn quote(ident: &str) -> String {
+                <$Backend as Dialect>::quote(ident)
+            }
+            fn limit_offset(limit: u64, offset: Option<u64>, ordered: bool) -> String {
+                <$Backend as Dialect>::limit_offset(limit, offset, ordered)
+            }
+            fn compound_part(query: &str) -> String {
+                <$Backend as Dialect>::compound_part(query)
+            }
+            fn null_check(expr: &str, null: bool) -> String {
+                <$Backend as Dialect>::null_check(expr, null)
+            }
+            fn boolean(value: bool) -> &'static str {
+                <$Backend as Dialect>::boolean(value)
+            }
+            fn delete_limit(table: &str, clause: Option<&str>, limit: u64) -> String {
+                <$Backend as Dialect>::delete_limit(table, clause, limit)
+            }
+            fn update_limit(table: &str, set: &str, clause: Option<&str>, limit: u64) -> String {
+                <$Backend as Dialect>::update_limit(table, set, clause, limit)
+            }
+            fn now() -> &'static str {
+                <$Backend as Dialect>::now()
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "sqlx", feature = "postgres"))]
+sqlx_dialect!(sqlx::Postgres, Postgres);
+
+#[cfg(all(feature = "sqlx", feature = "mysql"))]
+sqlx_dialect!(sqlx::MySql, MySql);
+
+#[cfg(all(feature = "sqlx", feature = "sqlite"))]
+sqlx_dialect!(sqlx::Sqlite, Sqlite);
+
+#[cfg(all(feature = "sqlx", feature = "mssql"))]
+sqlx_dialect!(sqlx::Mssql, Mssql);
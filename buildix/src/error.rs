@@ -2,12 +2,34 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum Error {
+    #[cfg(feature = "sqlx")]
     #[error("sqlx error: `{0}`")]
-    Sqlx(sqlx::error::Error),
+    Sqlx(#[from] sqlx::error::Error),
 
     #[error("map error: `{0}`")]
     MapError(Box<dyn std::error::Error>),
 
     #[error("filter error: `{0}`")]
     FilterError(Box<dyn std::error::Error>),
+
+    #[error("invalid field: `{0}`")]
+    InvalidField(String),
+
+    #[error("invalid group: `{0}`")]
+    InvalidGroup(String),
+
+    #[error("query without filter would affect all rows of `{0}` (use `allow_full_table`)")]
+    FullTable(String),
+
+    #[error("`{0}` is not supported by `{1}`")]
+    Unsupported(&'static str, &'static str),
+
+    #[error("scope `{0}` is not set")]
+    MissingScope(String),
+
+    #[error("invalid batch size: `{0}` (batch has to delete at least one row)")]
+    InvalidBatchSize(u64),
+
+    #[error("query error: `{0}`")]
+    Query(#[from] crate::query::QueryError),
 }
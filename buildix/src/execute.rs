@@ -1,14 +1,364 @@
 #![allow(unused_imports)]
 use async_trait::async_trait;
-use sqlx::database::Database;
-use sqlx::postgres::Postgres;
-use sqlx::Error;
-use sqlx::Pool;
+use std::sync::Arc;
+use std::time::Duration;
+use sqlx::database::{Database, HasArguments};
+use sqlx::{Arguments, Executor, FromRow, IntoArguments};
 
+use crate::delete::DeleteBuilder;
+use crate::dialect::Dialect;
+use crate::select::{with_projection_mask, Rows, SelectBuilder};
+use crate::value::{Type, Value};
+
+// Backend is sqlx database, which binds neutral values of queries
+pub trait Backend: Database + Dialect {
+    // arguments returns sqlx arguments of values (in order of placeholders), values of types
+    // which database does not support are errors
+    fn arguments<'q>(values: Vec<Value>) -> crate::Result<<Self as HasArguments<'q>>::Arguments>;
+
+    // rows_affected returns number of rows affected by query
+    fn rows_affected(result: &Self::QueryResult) -> u64;
+}
+
+// backend_impl implements backend for sqlx database. NULL is bound by its type, arms of types
+// supported only by some databases are given along with database.
+macro_rules! backend_impl {
+    ($DB:ty, $arguments:ident { $($arm:tt)* }) => {
+        impl Backend for $DB {
+            fn arguments<'q>(values: Vec<Value>) -> crate::Result<<Self as HasArguments<'q>>::Arguments> {
+                let mut $arguments = <Self as HasArguments<'q>>::Arguments::default();
+                for value in values {
+                    #[allow(unreachable_patterns)]
+                    match value {
+                        Value::Null(Type::Bool) => $arguments.add(None::<bool>),
+                        Value::Null(Type::I32) => $arguments.add(None::<i32>),
+                        Value::Null(Type::I64) => $arguments.add(None::<i64>),
+                        Value::Null(Type::F64) => $arguments.add(None::<f64>),
+                        Value::Null(Type::String) => $arguments.add(None::<String>),
+                        Value::Bool(value) => $arguments.add(value),
+                        Value::I32(value) => $arguments.add(value),
+                        Value::I64(value) => $arguments.add(value),
+                        Value::F64(value) => $arguments.add(value),
+                        Value::String(value) => $arguments.add(value),
+                        $($arm)*
+                        value => {
+                            return Err(crate::Error::Unsupported(
+                                value.get_type().name(),
+                                <Self as Dialect>::NAME,
+                            ))
+                        }
+                    }
+                }
+                Ok($arguments)
+            }
+
+            fn rows_affected(result: &Self::QueryResult) -> u64 {
+                result.rows_affected()
+            }
+        }
+    };
+    ($DB:ty) => {
+        backend_impl!($DB, arguments {
+            Value::Null(Type::Bytes) => arguments.add(None::<Vec<u8>>),
+            Value::Bytes(value) => arguments.add(value),
+            #[cfg(feature = "chrono")]
+            Value::Null(Type::Timestamp) => arguments.add(None::<chrono::DateTime<chrono::Utc>>),
+            #[cfg(feature = "chrono")]
+            Value::Timestamp(value) => arguments.add(value),
+            #[cfg(feature = "uuid")]
+            Value::Null(Type::Uuid) => arguments.add(None::<uuid::Uuid>),
+            #[cfg(feature = "uuid")]
+            Value::Uuid(value) => arguments.add(value),
+        });
+    };
+}
+
+#[cfg(feature = "postgres")]
+backend_impl!(sqlx::Postgres);
+
+#[cfg(feature = "mysql")]
+backend_impl!(sqlx::MySql);
+
+#[cfg(feature = "sqlite")]
+backend_impl!(sqlx::Sqlite);
+
+// mssql binds only basic types
+#[cfg(feature = "mssql")]
+backend_impl!(sqlx::Mssql, arguments {});
+
+// Fetch runs select builders. Executor can be pool, connection (`&mut PgConnection`) or
+// transaction (`&mut Transaction<'_, Postgres>`), so multiple builders can run atomically.
+// Fields removed by projection of builder are decoded as default values.
 #[async_trait]
-pub trait Execute {
-    // perform query
-    async fn execute<DB: Database, T>(&mut self, pool: Pool<DB>) -> Result<(), T>
+pub trait Fetch<T>: SelectBuilder + Rows<T> + Send
+where
+    T: Send + Unpin,
+{
+    // fetch_all returns all rows
+    async fn fetch_all<'c, DB, E>(&mut self, executor: E) -> crate::Result<Vec<T>>
+    where
+        DB: Backend,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        E: Executor<'c, Database = DB>,
+        T: for<'r> FromRow<'r, DB::Row>,
+    {
+        let (query, values) = self.to_sql::<DB>()?;
+        let mask = self.projection_mask();
+        let arguments = DB::arguments(values)?;
+        let rows = sqlx::query_with::<DB, _>(&query, arguments)
+            .fetch_all(executor)
+            .await?;
+        Ok(with_projection_mask(mask, || {
+            rows.iter().map(T::from_row).collect::<Result<Vec<T>, _>>()
+        })?)
+    }
+
+    // fetch_one returns single row (error when no row is found)
+    async fn fetch_one<'c, DB, E>(&mut self, executor: E) -> crate::Result<T>
     where
-        T: Into<crate::error::Error>;
+        DB: Backend,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        E: Executor<'c, Database = DB>,
+        T: for<'r> FromRow<'r, DB::Row>,
+    {
+        let (query, values) = self.to_sql::<DB>()?;
+        let mask = self.projection_mask();
+        let arguments = DB::arguments(values)?;
+        let row = sqlx::query_with::<DB, _>(&query, arguments)
+            .fetch_one(executor)
+            .await?;
+        Ok(with_projection_mask(mask, || T::from_row(&row))?)
+    }
+
+    // fetch_optional returns single row if found
+    async fn fetch_optional<'c, DB, E>(&mut self, executor: E) -> crate::Result<Option<T>>
+    where
+        DB: Backend,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        E: Executor<'c, Database = DB>,
+        T: for<'r> FromRow<'r, DB::Row>,
+    {
+        let (query, values) = self.to_sql::<DB>()?;
+        let mask = self.projection_mask();
+        let arguments = DB::arguments(values)?;
+        let row = sqlx::query_with::<DB, _>(&query, arguments)
+            .fetch_optional(executor)
+            .await?;
+        Ok(with_projection_mask(mask, || row.as_ref().map(T::from_row).transpose())?)
+    }
+}
+
+impl<B, T> Fetch<T> for B
+where
+    B: SelectBuilder + Rows<T> + Send,
+    T: Send + Unpin,
+{
+}
+
+// Batch configures execution in batches: number of rows deleted by single query, pause between
+// queries and cancellation hook (checked before every query)
+#[derive(Clone)]
+pub struct Batch {
+    size: u64,
+    pause: Option<Duration>,
+    cancel: Option<Arc<dyn Fn() -> bool + Send + Sync>>,
+}
+
+impl Batch {
+    pub fn new(size: u64) -> Self {
+        Self {
+            size,
+            pause: None,
+            cancel: None,
+        }
+    }
+
+    // pause sets pause between batches (so other queries can acquire locks)
+    pub fn pause(mut self, pause: Duration) -> Self {
+        self.pause = Some(pause);
+        self
+    }
+
+    // cancel sets hook which stops execution when it returns true
+    pub fn cancel<F>(mut self, cancel: F) -> Self
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.cancel = Some(Arc::new(cancel));
+        self
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|cancel| cancel())
+    }
 }
+
+impl From<u64> for Batch {
+    fn from(size: u64) -> Self {
+        Self::new(size)
+    }
+}
+
+impl std::fmt::Debug for Batch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Batch")
+            .field("size", &self.size)
+            .field("pause", &self.pause)
+            .field("cancel", &self.cancel.is_some())
+            .finish()
+    }
+}
+
+// Execute runs delete builders and returns number of affected rows (which is also set to
+// `#[buildix(count)]` field)
+#[async_trait]
+pub trait Execute: DeleteBuilder + Send {
+    async fn execute<'c, DB, E>(&mut self, executor: E) -> crate::Result<u64>
+    where
+        DB: Backend,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        E: Executor<'c, Database = DB>,
+    {
+        let (query, values) = self.to_sql::<DB>()?;
+        let arguments = DB::arguments(values)?;
+        let result = sqlx::query_with::<DB, _>(&query, arguments)
+            .execute(executor)
+            .await?;
+        let affected = DB::rows_affected(&result);
+        self.set_count(affected);
+        Ok(affected)
+    }
+
+    // execute_returning deletes rows and returns them by `RETURNING` clause (columns are given by
+    // `returning` of builder), error is returned when database does not support it
+    async fn execute_returning<'c, DB, E, T>(&mut self, executor: E) -> crate::Result<Vec<T>>
+    where
+        DB: Backend,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        E: Executor<'c, Database = DB>,
+        T: for<'r> FromRow<'r, DB::Row> + Send + Unpin,
+    {
+        let (query, values) = self.to_returning_sql::<DB>()?;
+        let arguments = DB::arguments(values)?;
+        let rows = sqlx::query_as_with::<DB, T, _>(&query, arguments)
+            .fetch_all(executor)
+            .await?;
+        self.set_count(rows.len() as u64);
+        Ok(rows)
+    }
+
+    // execute_in_batches deletes rows by queries limited to batch size until no row is affected,
+    // so tables are not locked by single huge query. Limit of builder caps total number of rows.
+    // Total is set to `#[buildix(count)]` field after every batch. Executor is connection (or
+    // transaction), since it is used repeatedly. Batch size has to be positive.
+    async fn execute_in_batches<DB, C, B>(&mut self, executor: &mut C, batch: B) -> crate::Result<u64>
+    where
+        DB: Backend,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        C: Send,
+        for<'e> &'e mut C: Executor<'e, Database = DB>,
+        B: Into<Batch> + Send,
+    {
+        let mut progress = Progress::new(batch.into(), self.get_limit())?;
+        while let Some(size) = progress.next_size() {
+            let (query, values) = self.to_raw_limited_sql::<DB>(Some(size))?;
+            let query = crate::dialect::number::<DB>(&query);
+            let arguments = DB::arguments(values)?;
+            let result = sqlx::query_with::<DB, _>(&query, arguments)
+                .execute(&mut *executor)
+                .await?;
+            if !progress.advance(DB::rows_affected(&result)) {
+                break;
+            }
+            self.set_count(progress.total);
+            progress.pause().await;
+        }
+
+        self.set_count(progress.total);
+        Ok(progress.total)
+    }
+
+    // execute_in_batches_pool is execute_in_batches with executor which is copied for every
+    // batch (`&Pool`), so batches may run on different connections of pool
+    async fn execute_in_batches_pool<'c, DB, E, B>(&mut self, executor: E, batch: B) -> crate::Result<u64>
+    where
+        DB: Backend,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        E: Executor<'c, Database = DB> + Copy,
+        B: Into<Batch> + Send,
+    {
+        let mut progress = Progress::new(batch.into(), self.get_limit())?;
+        while let Some(size) = progress.next_size() {
+            let (query, values) = self.to_raw_limited_sql::<DB>(Some(size))?;
+            let query = crate::dialect::number::<DB>(&query);
+            let arguments = DB::arguments(values)?;
+            let result = sqlx::query_with::<DB, _>(&query, arguments)
+                .execute(executor)
+                .await?;
+            if !progress.advance(DB::rows_affected(&result)) {
+                break;
+            }
+            self.set_count(progress.total);
+            progress.pause().await;
+        }
+
+        self.set_count(progress.total);
+        Ok(progress.total)
+    }
+}
+
+// Progress tracks execution in batches: size of next batch (limited by remaining rows of limit)
+// and total number of affected rows
+struct Progress {
+    batch: Batch,
+    remaining: Option<u64>,
+    total: u64,
+}
+
+impl Progress {
+    fn new(batch: Batch, limit: Option<u64>) -> crate::Result<Self> {
+        if batch.size == 0 {
+            return Err(crate::Error::InvalidBatchSize(batch.size));
+        }
+        Ok(Self {
+            batch,
+            remaining: limit,
+            total: 0,
+        })
+    }
+
+    // next_size returns size of next batch, None when execution is cancelled or limit is reached
+    fn next_size(&self) -> Option<u64> {
+        if self.batch.is_cancelled() {
+            return None;
+        }
+        let size = self
+            .remaining
+            .map_or(self.batch.size, |remaining| remaining.min(self.batch.size));
+        if size == 0 {
+            None
+        } else {
+            Some(size)
+        }
+    }
+
+    // advance adds rows affected by batch, false is returned when no row was affected
+    fn advance(&mut self, affected: u64) -> bool {
+        if affected == 0 {
+            return false;
+        }
+        self.total += affected;
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining = remaining.saturating_sub(affected);
+        }
+        true
+    }
+
+    async fn pause(&self) {
+        if let Some(pause) = self.batch.pause {
+            tokio::time::sleep(pause).await;
+        }
+    }
+}
+
+impl<B> Execute for B where B: DeleteBuilder + Send {}
@@ -2,19 +2,20 @@
 #![allow(unused_macros)]
 #![allow(unused_imports)]
 
-use sqlx::{Database, Type};
+use crate::dialect::Dialect;
+use crate::value::Value;
 
 // FilterResult returns sql clause as well as values assigned.
 #[derive(Default)]
 pub struct FilterResult {
     pub clause: String,
-    pub values: Vec<()>,
+    pub values: Vec<Value>,
     pub count: usize,
 }
 
 // FilterResult implementation
 impl FilterResult {
-    pub fn new(clause: String, values: Vec<()>, count: usize) -> Self {
+    pub fn new(clause: String, values: Vec<Value>, count: usize) -> Self {
         Self {
             clause: clause.trim().to_owned(),
             values,
@@ -27,26 +28,80 @@ impl FilterResult {
 #[derive(Clone, Debug, Default)]
 pub struct FilterInfo<'a> {
     pub ident: &'a str,
+
+    // column rendered in sql (`table.column`), ident is used when it's empty
+    pub column: &'a str,
+
     pub expr: Option<String>,
     pub counter: usize,
     pub isnull: bool,
 }
 
+impl FilterInfo<'_> {
+    // get_column returns column rendered in sql
+    pub fn get_column(&self) -> &str {
+        if self.column.is_empty() {
+            self.ident
+        } else {
+            self.column
+        }
+    }
+}
+
 // Filter trait
 pub trait Filter {
-    fn process_filter<DB: Database>(&self, info: &FilterInfo) -> Option<FilterResult>;
+    // number of values bound by filter expression, None when it's not known at compile time
+    // (or expression is not used)
+    const VALUES: Option<usize> = None;
+
+    // whether filter uses field name as column (checked against schema)
+    const COLUMN: bool = false;
+
+    // whether filter is null check itself, `None` of `Option` then means "either" (even with
+    // `isnull`), so `Option<IsNull>` is tri-state filter
+    const NULL_CHECK: bool = false;
+
+    fn process_filter<DB: Dialect>(&self, info: &FilterInfo) -> Option<FilterResult>;
+
+    // match_filter evaluates filter against row in memory, None is returned when filter is not
+    // set (the same way as process_filter). Filters with custom expression cannot be evaluated,
+    // derive then treats them as not matching when they are set.
+    fn match_filter<R: Fields + ?Sized>(&self, _row: &R, _info: &FilterInfo) -> Option<bool> {
+        None
+    }
+}
+
+// Fields exposes values of row by field names, so filters can be evaluated in memory. It is
+// implemented by selects, None is returned for unknown fields (or fields without value).
+pub trait Fields {
+    fn field(&self, name: &str) -> Option<Value>;
+}
+
+// and joins clauses by AND. Every clause is grouped when there are more of them, so OR in one of
+// them cannot escape the others (scope and soft delete conditions are always applied).
+pub fn and(clauses: &[String]) -> String {
+    match clauses {
+        [clause] => clause.clone(),
+        _ => clauses
+            .iter()
+            .map(|clause| format!("({})", clause))
+            .collect::<Vec<String>>()
+            .join(" AND "),
+    }
 }
 
 // Nullable is marker trait for fields that support `isnull`
 pub trait Nullable {}
 
 pub mod fields {
-    use super::{Filter, FilterInfo, FilterResult};
+    use super::{Fields, Filter, FilterInfo, FilterResult};
+    use crate::dialect::Dialect;
     use crate::filter::Nullable;
-    use sqlx::Database;
+    use crate::value::{ToValue, Value};
+    use std::cmp::Ordering;
 
-    // IsNull field that transforms into ISNULL, NOT ISNULL
-    // also works with Option seamlessly (as usual)
+    // IsNull field that transforms into null check of dialect (IS NULL, IS NOT NULL)
+    // Option<IsNull> is tri-state: None (either), Some(true) (null), Some(false) (not null)
     #[derive(Debug, Default, Eq, PartialEq)]
     pub struct IsNull(bool);
 
@@ -61,19 +116,163 @@ pub mod fields {
 
     // implement filter for isnull
     impl Filter for IsNull {
-        fn process_filter<DB: Database>(&self, info: &FilterInfo) -> Option<FilterResult> {
-            match self.0 {
-                true => Some(FilterResult::new(
-                    format!("{} ISNULL", info.ident),
-                    vec![],
-                    1,
-                )),
-                false => Some(FilterResult::new(
-                    format!("{} NOT ISNULL", info.ident),
-                    vec![],
-                    1,
+        const COLUMN: bool = true;
+        const NULL_CHECK: bool = true;
+
+        fn process_filter<DB: Dialect>(&self, info: &FilterInfo) -> Option<FilterResult> {
+            Some(FilterResult::new(
+                DB::null_check(info.get_column(), self.0),
+                vec![],
+                1,
+            ))
+        }
+
+        fn match_filter<R: Fields + ?Sized>(&self, row: &R, info: &FilterInfo) -> Option<bool> {
+            row.field(info.ident)
+                .map(|value| value.is_null() == self.0)
+                .or(Some(false))
+        }
+    }
+
+    // Op is comparison of column with value (operator suffix of query parameter, e.g. `age__gt`)
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Op<T> {
+        Eq(T),
+        Ne(T),
+        Gt(T),
+        Gte(T),
+        Lt(T),
+        Lte(T),
+        In(Vec<T>),
+        Contains(String),
+        IContains(String),
+        IsNull(bool),
+    }
+
+    // Ops field transforms into comparisons joined by AND, empty ops are not written
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Ops<T>(pub Vec<Op<T>>);
+
+    impl<T> Default for Ops<T> {
+        fn default() -> Self {
+            Self(vec![])
+        }
+    }
+
+    impl<T> Ops<T> {
+        pub fn push(&mut self, op: Op<T>) {
+            self.0.push(op);
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.0.is_empty()
+        }
+    }
+
+    impl<T> From<Vec<Op<T>>> for Ops<T> {
+        fn from(ops: Vec<Op<T>>) -> Self {
+            Self(ops)
+        }
+    }
+
+    // implement filter for ops (column is always compared, custom expression is not used)
+    impl<T> Filter for Ops<T>
+    where
+        T: ToValue,
+    {
+        const COLUMN: bool = true;
+
+        fn process_filter<DB: Dialect>(&self, info: &FilterInfo) -> Option<FilterResult> {
+            let ident = info.get_column();
+            let mut clauses: Vec<String> = vec![];
+            let mut values: Vec<Value> = vec![];
+
+            for op in &self.0 {
+                let (operator, value) = match op {
+                    Op::Eq(value) => ("=", value),
+                    Op::Ne(value) => ("<>", value),
+                    Op::Gt(value) => (">", value),
+                    Op::Gte(value) => (">=", value),
+                    Op::Lt(value) => ("<", value),
+                    Op::Lte(value) => ("<=", value),
+                    Op::In(list) => {
+                        if !list.is_empty() {
+                            let placeholders: Vec<&str> = list.iter().map(|_| "?").collect();
+                            clauses.push(format!("{} IN ({})", ident, placeholders.join(", ")));
+                            values.extend(list.iter().map(|v| v.to_value()));
+                        }
+                        continue;
+                    }
+                    // % and _ in value are wildcards
+                    Op::Contains(value) => {
+                        clauses.push(format!("{} LIKE ?", ident));
+                        values.push(Value::String(format!("%{}%", value)));
+                        continue;
+                    }
+                    Op::IContains(value) => {
+                        clauses.push(format!("LOWER({}) LIKE ?", ident));
+                        values.push(Value::String(format!("%{}%", value.to_lowercase())));
+                        continue;
+                    }
+                    Op::IsNull(null) => {
+                        clauses.push(DB::null_check(ident, *null));
+                        continue;
+                    }
+                };
+                clauses.push(format!("{} {} ?", ident, operator));
+                values.push(value.to_value());
+            }
+
+            match clauses.len() {
+                0 => None,
+                1 => Some(FilterResult::new(clauses.remove(0), values, 1)),
+                len => Some(FilterResult::new(
+                    format!("({})", clauses.join(" AND ")),
+                    values,
+                    len,
                 )),
             }
         }
+
+        fn match_filter<R: Fields + ?Sized>(&self, row: &R, info: &FilterInfo) -> Option<bool> {
+            let field = row.field(info.ident);
+            let mut results: Vec<bool> = vec![];
+
+            for op in &self.0 {
+                let compare = |value: &T, expected: &[Ordering]| {
+                    field
+                        .as_ref()
+                        .and_then(|f| f.compare(&value.to_value()))
+                        .is_some_and(|ordering| expected.contains(&ordering))
+                };
+                let contains = |value: &str, lowercase: bool| match &field {
+                    Some(Value::String(f)) if lowercase => {
+                        f.to_lowercase().contains(&value.to_lowercase())
+                    }
+                    Some(Value::String(f)) => f.contains(value),
+                    _ => false,
+                };
+
+                results.push(match op {
+                    Op::Eq(value) => compare(value, &[Ordering::Equal]),
+                    Op::Ne(value) => compare(value, &[Ordering::Less, Ordering::Greater]),
+                    Op::Gt(value) => compare(value, &[Ordering::Greater]),
+                    Op::Gte(value) => compare(value, &[Ordering::Greater, Ordering::Equal]),
+                    Op::Lt(value) => compare(value, &[Ordering::Less]),
+                    Op::Lte(value) => compare(value, &[Ordering::Less, Ordering::Equal]),
+                    Op::In(list) if list.is_empty() => continue,
+                    Op::In(list) => list.iter().any(|v| compare(v, &[Ordering::Equal])),
+                    Op::Contains(value) => contains(value, false),
+                    Op::IContains(value) => contains(value, true),
+                    Op::IsNull(null) => field.as_ref().is_some_and(|f| f.is_null() == *null),
+                });
+            }
+
+            if results.is_empty() {
+                None
+            } else {
+                Some(results.iter().all(|r| *r))
+            }
+        }
     }
 }
@@ -1,22 +1,36 @@
 pub mod count;
 pub mod delete;
+#[cfg(feature = "describe")]
+pub mod describe;
+pub mod dialect;
 pub mod error;
+#[cfg(feature = "sqlx")]
 pub mod execute;
 pub mod filter;
 pub mod limit;
 pub mod offset;
 pub mod prelude;
+pub mod query;
 pub mod result;
+pub mod scope;
 pub mod select;
 pub mod sort;
+pub mod value;
 
 pub use crate::count::Count;
 pub use crate::delete::DeleteBuilder;
+pub use crate::dialect::Dialect;
 pub use crate::error::Error;
 pub use crate::filter::Filter;
 pub use crate::limit::Limit;
+pub use crate::query::{FromQuery, QueryError};
 pub use crate::result::Result;
 pub use crate::select::{Select, SelectBuilder};
+pub use crate::value::{ToValue, Value};
+
+// generated code decodes rows by sqlx of buildix
+#[cfg(feature = "sqlx")]
+pub use sqlx;
 
 #[cfg(test)]
 mod tests {
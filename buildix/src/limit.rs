@@ -1,17 +1,16 @@
-use sqlx::Database;
-
+// Limit is implemented by types that limit number of rows, clause is rendered by dialect
 pub trait Limit {
-    fn get_limit<DB: Database>(self) -> Option<String>;
+    fn get_limit(self) -> Option<u64>;
 }
 
 macro_rules! impl_limit {
     ($T:ty) => {
         impl Limit for $T {
-            fn get_limit<DB: Database>(self) -> Option<String> {
+            fn get_limit(self) -> Option<u64> {
                 if self < 0 {
                     None
                 } else {
-                    Some(format!("LIMIT {}", self))
+                    Some(self as u64)
                 }
             }
         }
@@ -25,9 +24,9 @@ impl<T> Limit for Option<T>
 where
     T: Limit,
 {
-    fn get_limit<DB: Database>(self) -> Option<String> {
+    fn get_limit(self) -> Option<u64> {
         match self {
-            Some(t) => t.get_limit::<DB>(),
+            Some(t) => t.get_limit(),
             None => None,
         }
     }
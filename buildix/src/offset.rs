@@ -1,17 +1,16 @@
-use sqlx::Database;
-
+// Offset is implemented by types that skip rows, clause is rendered by dialect (along with limit)
 pub trait Offset {
-    fn get_offset<DB: Database>(self) -> Option<String>;
+    fn get_offset(self) -> Option<u64>;
 }
 
 macro_rules! impl_offset {
     ($T:ty) => {
         impl Offset for $T {
-            fn get_offset<DB: Database>(self) -> Option<String> {
+            fn get_offset(self) -> Option<u64> {
                 if self <= 0 {
                     None
                 } else {
-                    Some(format!("OFFSET {}", self))
+                    Some(self as u64)
                 }
             }
         }
@@ -25,9 +24,9 @@ impl<T> Offset for Option<T>
 where
     T: Offset,
 {
-    fn get_offset<DB: Database>(self) -> Option<String> {
+    fn get_offset(self) -> Option<u64> {
         match self {
-            Some(t) => t.get_offset::<DB>(),
+            Some(t) => t.get_offset(),
             None => None,
         }
     }
@@ -1,23 +1,37 @@
 #![allow(unused_imports)]
 
 pub use super::delete::DeleteBuilder;
+pub use super::query::FromQuery;
 pub use super::select::{Select, SelectBuilder};
 pub use super::sort::Sort;
 use crate::filter::Nullable;
-use crate::filter::{Filter, FilterInfo, FilterResult};
-use sqlx::database::Database;
+use crate::filter::{Fields, Filter, FilterInfo, FilterResult};
+use crate::value::ToValue;
+use crate::dialect::Dialect;
 
 #[macro_export]
 macro_rules! filter_impl {
     {$A:ty} => {
         impl Filter for $A {
-            fn process_filter<DB: Database>(&self, fi: &FilterInfo) -> Option<FilterResult> {
+            const VALUES: Option<usize> = Some(1);
+            const COLUMN: bool = true;
+
+            fn process_filter<DB: Dialect>(&self, fi: &FilterInfo) -> Option<FilterResult> {
                 // now we are not none
                 if let Some(expr) = &fi.expr {
-                    Some(FilterResult::new(expr.clone(), vec![()], 1))
+                    Some(FilterResult::new(expr.clone(), vec![self.to_value()], 1))
                 } else {
-                    Some(FilterResult::new(format!("{} = ?", fi.ident), vec![()], 1))
+                    Some(FilterResult::new(format!("{} = ?", fi.get_column()), vec![self.to_value()], 1))
+                }
+            }
+
+            fn match_filter<R: Fields + ?Sized>(&self, row: &R, fi: &FilterInfo) -> Option<bool> {
+                // custom expressions cannot be evaluated
+                if fi.expr.is_some() {
+                    return None;
                 }
+                let value = self.to_value();
+                Some(row.field(fi.ident).is_some_and(|v| v.matches(&value)))
             }
         }
     };
@@ -31,20 +45,43 @@ filter_impl!(String);
 filter_impl!(&String);
 filter_impl!(&str);
 
+// bool is rendered as boolean literal of dialect (`1`/`0` on mssql), only expressions bind it
+impl Filter for bool {
+    const VALUES: Option<usize> = Some(1);
+    const COLUMN: bool = true;
+
+    fn process_filter<DB: Dialect>(&self, fi: &FilterInfo) -> Option<FilterResult> {
+        if let Some(expr) = &fi.expr {
+            Some(FilterResult::new(expr.clone(), vec![self.to_value()], 1))
+        } else {
+            Some(FilterResult::new(format!("{} = {}", fi.get_column(), DB::boolean(*self)), vec![], 1))
+        }
+    }
+
+    fn match_filter<R: Fields + ?Sized>(&self, row: &R, fi: &FilterInfo) -> Option<bool> {
+        // custom expressions cannot be evaluated
+        if fi.expr.is_some() {
+            return None;
+        }
+        let value = self.to_value();
+        Some(row.field(fi.ident).is_some_and(|v| v.matches(&value)))
+    }
+}
+
 // implement filter for option
 impl<T> Filter for Option<T>
 where
     T: Filter,
 {
-    fn process_filter<DB: Database>(&self, fi: &FilterInfo) -> Option<FilterResult> {
+    const VALUES: Option<usize> = T::VALUES;
+    const COLUMN: bool = T::COLUMN;
+    const NULL_CHECK: bool = T::NULL_CHECK;
+
+    fn process_filter<DB: Dialect>(&self, fi: &FilterInfo) -> Option<FilterResult> {
         match self {
             None => {
-                if fi.isnull {
-                    Some(FilterResult::new(
-                        format!("{} ISNULL", fi.ident).to_string(),
-                        vec![],
-                        1,
-                    ))
+                if fi.isnull && !T::NULL_CHECK {
+                    Some(FilterResult::new(DB::null_check(fi.get_column(), true), vec![], 1))
                 } else {
                     None
                 }
@@ -52,6 +89,19 @@ where
             Some(val) => val.process_filter::<DB>(fi),
         }
     }
+
+    fn match_filter<R: Fields + ?Sized>(&self, row: &R, fi: &FilterInfo) -> Option<bool> {
+        match self {
+            None => {
+                if fi.isnull && !T::NULL_CHECK {
+                    Some(row.field(fi.ident).is_some_and(|v| v.is_null()))
+                } else {
+                    None
+                }
+            }
+            Some(val) => val.match_filter(row, fi),
+        }
+    }
 }
 
 // Option is nullable
@@ -60,19 +110,30 @@ impl<T> Nullable for Option<T> {}
 // add IN
 impl<T> Filter for Vec<T>
 where
-    T: Filter,
+    T: Filter + ToValue,
 {
-    fn process_filter<DB: Database>(&self, info: &FilterInfo) -> Option<FilterResult> {
+    const COLUMN: bool = true;
+
+    fn process_filter<DB: Dialect>(&self, info: &FilterInfo) -> Option<FilterResult> {
         let len = self.len();
         if len == 0 {
             None
         } else {
-            let placeholders: Vec<String> = [0..len].iter().map(|_| "?".to_string()).collect();
+            let placeholders: Vec<String> = (0..len).map(|_| "?".to_string()).collect();
             Some(FilterResult::new(
-                format!("{} IN ({})", info.ident, placeholders.join(", ")),
-                vec![(); len],
+                format!("{} IN ({})", info.get_column(), placeholders.join(", ")),
+                self.iter().map(|v| v.to_value()).collect(),
                 len,
             ))
         }
     }
+
+    fn match_filter<R: Fields + ?Sized>(&self, row: &R, info: &FilterInfo) -> Option<bool> {
+        if self.is_empty() {
+            None
+        } else {
+            let field = row.field(info.ident);
+            Some(field.is_some_and(|f| self.iter().any(|v| f.matches(&v.to_value()))))
+        }
+    }
 }
@@ -0,0 +1,237 @@
+use thiserror::Error;
+
+use crate::filter::fields::{IsNull, Op, Ops};
+
+// operator suffixes of query parameters (`age__gt=18`)
+pub const OPS: &[&str] = &[
+    "eq",
+    "ne",
+    "gt",
+    "gte",
+    "lt",
+    "lte",
+    "in",
+    "contains",
+    "icontains",
+    "isnull",
+];
+
+// is_op returns whether operator suffix is supported, derive checks `ops` of fields by it in
+// compile time (so list of operators is defined only here)
+pub const fn is_op(op: &str) -> bool {
+    has_op(OPS, op)
+}
+
+// has_op returns whether operator is listed, derive checks `ops` of fields against operators of
+// their types (`FromQuery::OPS`) by it in compile time
+pub const fn has_op(ops: &[&str], op: &str) -> bool {
+    let mut i = 0;
+    while i < ops.len() {
+        if str_eq(ops[i], op) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+// str_eq compares strings in const context
+pub(crate) const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// QueryError names query parameter which could not be set to filter
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("invalid query parameter `{param}`: {message}")]
+pub struct QueryError {
+    pub param: String,
+    pub message: String,
+}
+
+// FromParam parses value of query parameter
+pub trait FromParam: Sized {
+    fn from_param(value: &str) -> Result<Self, String>;
+}
+
+macro_rules! from_param_impl {
+    {$T:ty} => {
+        impl FromParam for $T {
+            fn from_param(value: &str) -> Result<Self, String> {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|err| format!("invalid value `{}`: {}", value, err))
+            }
+        }
+    };
+}
+
+from_param_impl!(i32);
+from_param_impl!(i64);
+
+impl FromParam for String {
+    fn from_param(value: &str) -> Result<Self, String> {
+        Ok(value.to_string())
+    }
+}
+
+impl FromParam for bool {
+    fn from_param(value: &str) -> Result<Self, String> {
+        match value.trim() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(format!("invalid boolean `{}`", value)),
+        }
+    }
+}
+
+impl FromParam for IsNull {
+    fn from_param(value: &str) -> Result<Self, String> {
+        bool::from_param(value).map(IsNull::from)
+    }
+}
+
+// FromQuery sets filter from query parameters. Parameters are named by fields of filter, nested
+// filters are addressed by prefix (`inner.value=1`) and operator follows double underscore
+// (`age__gt=18`). It is implemented by filters with `#[buildix(from_query)]`, operators allowed
+// for field are listed by `#[buildix(ops = "gt, lt")]`.
+pub trait FromQuery {
+    // operators supported by type (`eq` is always allowed)
+    const OPS: &'static [&'static str] = &["eq"];
+
+    // set_param sets value of parameter, path is name relative to filter (empty for fields)
+    fn set_param(&mut self, path: &str, op: Option<&str>, value: &str) -> Result<(), String>;
+
+    // from_query returns filter set from url encoded query string
+    fn from_query(query: &str) -> Result<Self, QueryError>
+    where
+        Self: Default + Sized,
+    {
+        let mut filter = Self::default();
+        parse_query(&mut filter, query)?;
+        Ok(filter)
+    }
+}
+
+// parse_query sets filter from url encoded query string, error names the first invalid parameter
+pub fn parse_query<F: FromQuery + ?Sized>(filter: &mut F, query: &str) -> Result<(), QueryError> {
+    let query = query.strip_prefix('?').unwrap_or(query);
+
+    for (name, value) in form_urlencoded::parse(query.as_bytes()) {
+        let (path, op) = match name.rsplit_once("__") {
+            Some((path, op)) if !path.is_empty() && !op.contains('.') => (path, Some(op)),
+            _ => (name.as_ref(), None),
+        };
+        filter
+            .set_param(path, op, &value)
+            .map_err(|message| QueryError {
+                param: name.to_string(),
+                message,
+            })?;
+    }
+
+    Ok(())
+}
+
+// check_param checks that parameter addresses field itself and operator is supported by field
+fn check_param(path: &str, op: Option<&str>, supported: &[&str]) -> Result<(), String> {
+    if !path.is_empty() {
+        return Err(format!("unknown field `{}`", path));
+    }
+    match op {
+        Some(op) if !supported.contains(&op) => Err(format!("unsupported operator `{}`", op)),
+        _ => Ok(()),
+    }
+}
+
+// values splits comma separated list of values
+fn values<T: FromParam>(value: &str) -> Result<Vec<T>, String> {
+    value
+        .split(',')
+        .filter(|v| !v.trim().is_empty())
+        .map(T::from_param)
+        .collect()
+}
+
+macro_rules! from_query_impl {
+    {$T:ty, $($op:literal),*} => {
+        impl FromQuery for $T {
+            const OPS: &'static [&'static str] = &[$($op),*];
+
+            fn set_param(&mut self, path: &str, op: Option<&str>, value: &str) -> Result<(), String> {
+                check_param(path, op, Self::OPS)?;
+                *self = FromParam::from_param(value)?;
+                Ok(())
+            }
+        }
+    };
+}
+
+from_query_impl!(i32, "eq");
+from_query_impl!(i64, "eq");
+from_query_impl!(String, "eq");
+from_query_impl!(bool, "eq");
+from_query_impl!(IsNull, "eq", "isnull");
+
+impl<T> FromQuery for Option<T>
+where
+    T: FromQuery + Default,
+{
+    const OPS: &'static [&'static str] = T::OPS;
+
+    fn set_param(&mut self, path: &str, op: Option<&str>, value: &str) -> Result<(), String> {
+        let mut inner = self.take().unwrap_or_default();
+        inner.set_param(path, op, value)?;
+        *self = Some(inner);
+        Ok(())
+    }
+}
+
+// values of repeated parameters (`id=1&id=2`) are appended
+impl<T> FromQuery for Vec<T>
+where
+    T: FromParam,
+{
+    const OPS: &'static [&'static str] = &["eq", "in"];
+
+    fn set_param(&mut self, path: &str, op: Option<&str>, value: &str) -> Result<(), String> {
+        check_param(path, op, Self::OPS)?;
+        self.extend(values(value)?);
+        Ok(())
+    }
+}
+
+impl<T> FromQuery for Ops<T>
+where
+    T: FromParam,
+{
+    const OPS: &'static [&'static str] = OPS;
+
+    fn set_param(&mut self, path: &str, op: Option<&str>, value: &str) -> Result<(), String> {
+        check_param(path, op, Self::OPS)?;
+        self.push(match op.unwrap_or("eq") {
+            "ne" => Op::Ne(T::from_param(value)?),
+            "gt" => Op::Gt(T::from_param(value)?),
+            "gte" => Op::Gte(T::from_param(value)?),
+            "lt" => Op::Lt(T::from_param(value)?),
+            "lte" => Op::Lte(T::from_param(value)?),
+            "in" => Op::In(values(value)?),
+            "contains" => Op::Contains(value.to_string()),
+            "icontains" => Op::IContains(value.to_string()),
+            "isnull" => Op::IsNull(bool::from_param(value)?),
+            _ => Op::Eq(T::from_param(value)?),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::value::{ToValue, Value};
+
+// Scope is implemented by values of `#[buildix(scope)]` fields, None means that scope is not set
+// (and query cannot be rendered)
+pub trait Scope {
+    fn get_scope(&self) -> Option<Value>;
+}
+
+impl<T> Scope for Option<T>
+where
+    T: ToValue,
+{
+    fn get_scope(&self) -> Option<Value> {
+        self.as_ref().map(|value| value.to_value())
+    }
+}
+
+// scope_clause returns clause of scope column along with its value, error is returned when scope
+// is not set
+pub fn scope_clause<S: Scope + ?Sized>(column: &str, scope: &S) -> crate::Result<(String, Value)> {
+    match scope.get_scope() {
+        Some(value) => Ok((format!("{} = ?", column), value)),
+        None => Err(crate::Error::MissingScope(column.to_string())),
+    }
+}
+
+// qualify returns scope column qualified by main table of select (see `Select::QUALIFIER`),
+// already qualified columns are kept
+pub fn qualify(column: &str, qualifier: Option<&str>) -> String {
+    match qualifier {
+        Some(qualifier) if !column.contains('.') => format!("{}.{}", qualifier, column),
+        _ => column.to_string(),
+    }
+}
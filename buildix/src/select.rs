@@ -1,53 +1,674 @@
 #![allow(unused_imports)]
 
-use async_trait::async_trait;
-use sqlx::database::Database;
+use crate::dialect::{self, Dialect};
+use crate::value::Value;
 
-#[cfg(feature = "postgres")]
-use sqlx::postgres::Postgres;
+#[cfg(feature = "sqlx")]
+use sqlx::database::{Database, HasArguments};
+#[cfg(feature = "sqlx")]
+use sqlx::decode::Decode;
+#[cfg(feature = "sqlx")]
+use sqlx::types::Type;
+#[cfg(feature = "sqlx")]
+use sqlx::{ColumnIndex, Error, Executor, FromRow, IntoArguments, Pool, Row};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::{Mutex, OnceLock};
 
-#[cfg(feature = "mysql")]
-use sqlx::mysql::MySql;
-
-use sqlx::Error;
-use sqlx::Pool;
+use crate::limit::Limit;
+use crate::offset::Offset;
+use crate::sort::{Sort, Sorter};
 
 // select query implementation
 pub trait SelectBuilder {
-    // returns query
-    fn to_sql<DB: Database>(&mut self) -> crate::Result<(String, Vec<()>)>;
+    // returns query with `?` placeholders (so it can be embedded into another query)
+    fn to_raw_sql<DB: Dialect>(&mut self) -> crate::Result<(String, Vec<Value>)>;
+
+    // returns query with placeholders of database
+    fn to_sql<DB: Dialect>(&mut self) -> crate::Result<(String, Vec<Value>)> {
+        let (query, values) = self.to_raw_sql::<DB>()?;
+        Ok((dialect::number::<DB>(&query), values))
+    }
+
+    // projection_mask returns names of fields which are not selected by projection
+    fn projection_mask(&self) -> Vec<&'static str> {
+        vec![]
+    }
 }
 
-// Query trait
-// @TODO: change to static methods
+// Select describes query of select builder. Everything is static, so no row instance is needed.
+// Values that depend on database are provided by static methods, others by associated consts.
 pub trait Select {
-    fn get_fields<DB: Database>(&self) -> &'static [&'static str];
-    fn get_fields_str<DB: Database>(&self) -> &'static str;
-    fn get_table<DB: Database>(&self) -> &'static str;
-    fn get_query<DB: Database>(&self) -> &'static str;
-    fn get_group<DB: Database>(&mut self) -> Option<&'static str>;
+    // row type returned by query
+    type Row;
+
+    // whether select is always distinct
+    const DISTINCT: bool;
+
+    // column marking soft deleted rows, rows where it is not NULL are excluded by builders
+    const SOFT_DELETE: Option<&'static str> = None;
+
+    // whether soft delete column is boolean flag (instead of timestamp of deletion)
+    const SOFT_DELETE_FLAG: bool = false;
+
+    // alias (or table name when joined) which qualifies columns of main table, None when columns
+    // are unambiguous
+    const QUALIFIER: Option<&'static str> = None;
+
+    // names of fields declared by select (flattened fields are not included)
+    const FIELDS: &'static [&'static str] = &[];
+
+    // entries of static group (expressions of `GROUP BY`)
+    const GROUP: &'static [&'static str] = &[];
+
+    // columns of select (flattened structs are already inlined)
+    fn get_columns() -> &'static [Column];
+
+    // field names (in the same order as fields)
+    fn get_field_names() -> &'static [&'static str];
+
+    fn get_fields<DB: Dialect>() -> &'static [&'static str];
+    fn get_fields_str<DB: Dialect>() -> &'static str;
+    fn get_table<DB: Dialect>() -> &'static str;
+    fn get_query<DB: Dialect>() -> &'static str;
+    fn get_query_distinct<DB: Dialect>() -> &'static str;
+    fn get_group<DB: Dialect>() -> Option<&'static str>;
+
+    // get_with returns `WITH ...` clause (common table expressions) along with its values.
+    // Builders of expressions are provided by select builder (by name), default ones are used
+    // otherwise.
+    fn get_with<DB: Dialect>(
+        builders: &mut WithBuilders<'_>,
+    ) -> crate::Result<Option<(String, Vec<Value>)>>;
+}
+
+// WithBuilders renders query of common table expression by its name, None is returned when
+// builder is not provided (`#[buildix(with = "name")]` field of select builder)
+pub type WithBuilders<'a> = dyn FnMut(&str) -> Option<crate::Result<(String, Vec<Value>)>> + 'a;
+
+// select_impl implements Select for containers of rows (Vec<T>, Option<T>) by delegating to T
+macro_rules! select_impl {
+    ($T:ty) => {
+        impl<T> Select for $T
+        where
+            T: Select,
+        {
+            type Row = T::Row;
+
+            const DISTINCT: bool = T::DISTINCT;
+            const SOFT_DELETE: Option<&'static str> = T::SOFT_DELETE;
+            const SOFT_DELETE_FLAG: bool = T::SOFT_DELETE_FLAG;
+            const QUALIFIER: Option<&'static str> = T::QUALIFIER;
+            const FIELDS: &'static [&'static str] = T::FIELDS;
+            const GROUP: &'static [&'static str] = T::GROUP;
+
+            #[inline]
+            fn get_columns() -> &'static [Column] {
+                T::get_columns()
+            }
+            #[inline]
+            fn get_field_names() -> &'static [&'static str] {
+                T::get_field_names()
+            }
+
+            #[inline]
+            fn get_fields<DB: Dialect>() -> &'static [&'static str] {
+                T::get_fields::<DB>()
+            }
+            #[inline]
+            fn get_fields_str<DB: Dialect>() -> &'static str {
+                T::get_fields_str::<DB>()
+            }
+            #[inline]
+            fn get_table<DB: Dialect>() -> &'static str {
+                T::get_table::<DB>()
+            }
+            #[inline]
+            fn get_query<DB: Dialect>() -> &'static str {
+                T::get_query::<DB>()
+            }
+            #[inline]
+            fn get_query_distinct<DB: Dialect>() -> &'static str {
+                T::get_query_distinct::<DB>()
+            }
+            #[inline]
+            fn get_group<DB: Dialect>() -> Option<&'static str> {
+                T::get_group::<DB>()
+            }
+            #[inline]
+            fn get_with<DB: Dialect>(
+                builders: &mut WithBuilders<'_>,
+            ) -> crate::Result<Option<(String, Vec<Value>)>> {
+                T::get_with::<DB>(builders)
+            }
+        }
+    };
+}
+
+select_impl!(Vec<T>);
+select_impl!(Option<T>);
+
+// Column is single column of select. Name is always the column name in result (alias).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Column {
+    pub name: &'static str,
+    pub table: &'static str,
+    pub column: &'static str,
+    pub expr: &'static str,
+}
+
+impl Column {
+    // render returns column as used in select (e.g. `u.name AS u_name`)
+    pub fn render(&self) -> String {
+        if !self.expr.is_empty() {
+            return format!("{} AS {}", self.expr, self.name);
+        }
+
+        let mut rendered = self.column.to_string();
+        if !self.table.is_empty() {
+            rendered = format!("{}.{}", self.table, rendered);
+        }
+        if self.column != self.name {
+            rendered = format!("{} AS {}", rendered, self.name);
+        }
+        rendered
+    }
+
+    // group_expr returns column as used in GROUP BY (e.g. `u.name`)
+    pub fn group_expr(&self) -> Cow<'static, str> {
+        if !self.expr.is_empty() {
+            Cow::Borrowed(self.expr)
+        } else if !self.table.is_empty() {
+            Cow::Owned(format!("{}.{}", self.table, self.column))
+        } else {
+            Cow::Borrowed(self.column)
+        }
+    }
+
+    // flatten returns column of flattened struct, name is prefixed and table is replaced
+    // (when given). Expressions are kept as they are.
+    pub fn flatten(&self, prefix: &str, table: &'static str) -> Column {
+        let mut column = *self;
+        column.name = Box::leak(format!("{}{}", prefix, self.name).into_boxed_str());
+        if !table.is_empty() && column.expr.is_empty() {
+            column.table = table;
+        }
+        column
+    }
+}
+
+// Rendered holds select parts rendered at runtime, used by selects with flattened structs.
+// It is computed once per select type, so leaking strings is bounded.
+#[derive(Debug)]
+pub struct Rendered {
+    pub columns: &'static [Column],
+    pub names: &'static [&'static str],
+    pub fields: &'static [&'static str],
+    pub fields_str: &'static str,
+    pub query: &'static str,
+    pub query_distinct: &'static str,
+}
+
+impl Rendered {
+    pub fn new(columns: Vec<Column>, table: &str, distinct: bool) -> Self {
+        let names: Vec<&'static str> = columns.iter().map(|c| c.name).collect();
+        let fields: Vec<&'static str> = columns
+            .iter()
+            .map(|c| &*Box::leak(c.render().into_boxed_str()))
+            .collect();
+        let fields_str = fields.join(", ");
+        let query_distinct = format!("SELECT DISTINCT {} FROM {}", fields_str, table);
+        let query = if distinct {
+            query_distinct.clone()
+        } else {
+            format!("SELECT {} FROM {}", fields_str, table)
+        };
+
+        Self {
+            columns: Box::leak(columns.into_boxed_slice()),
+            names: Box::leak(names.into_boxed_slice()),
+            fields: Box::leak(fields.into_boxed_slice()),
+            fields_str: Box::leak(fields_str.into_boxed_str()),
+            query: Box::leak(query.into_boxed_str()),
+            query_distinct: Box::leak(query_distinct.into_boxed_str()),
+        }
+    }
+}
+
+// Projection is implemented by types that can request subset of select fields (by field name)
+pub trait Projection {
+    // requested field names, empty means all fields
+    fn requested(&self) -> &[String];
+}
+
+impl Projection for Vec<String> {
+    fn requested(&self) -> &[String] {
+        self
+    }
+}
+
+impl<T> Projection for Option<T>
+where
+    T: Projection,
+{
+    fn requested(&self) -> &[String] {
+        match self {
+            Some(t) => t.requested(),
+            None => &[],
+        }
+    }
+}
+
+// project returns fields for requested names (in order of select fields), or None when all
+// fields should be selected. Requested names are whitelisted by select field names.
+pub fn project(
+    names: &[&str],
+    fields: &[&str],
+    requested: &[String],
+) -> crate::Result<Option<String>> {
+    if requested.is_empty() {
+        return Ok(None);
+    }
+
+    // check that all requested fields are available
+    for name in requested {
+        if !names.contains(&name.trim()) {
+            return Err(crate::Error::InvalidField(name.clone()));
+        }
+    }
+
+    let projected: Vec<&str> = names
+        .iter()
+        .zip(fields.iter())
+        .filter(|(name, _)| requested.iter().any(|r| r.trim() == **name))
+        .map(|(_, field)| *field)
+        .collect();
+
+    Ok(Some(projected.join(", ")))
+}
+
+// projection_mask returns names of fields which are not selected for requested names
+pub fn projection_mask(names: &'static [&'static str], requested: &[String]) -> Vec<&'static str> {
+    if requested.is_empty() {
+        return vec![];
+    }
+
+    names
+        .iter()
+        .filter(|name| !requested.iter().any(|r| r.trim() == **name))
+        .copied()
+        .collect()
+}
+
+thread_local! {
+    // names of columns which are not selected by projection of rows being decoded
+    static PROJECTION_MASK: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+// with_projection_mask runs decode of rows selected with given projection mask, so columns
+// removed by projection are decoded as default values (see `get_column_or_default`)
+pub fn with_projection_mask<R>(mask: Vec<&'static str>, decode: impl FnOnce() -> R) -> R {
+    // previous mask is restored even when decode panics
+    struct Restore(Vec<&'static str>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = std::mem::take(&mut self.0);
+            PROJECTION_MASK.with(|m| *m.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(PROJECTION_MASK.with(|m| m.replace(mask)));
+    decode()
+}
+
+// is_projected_out returns whether column is removed by projection of rows being decoded
+#[cfg(feature = "sqlx")]
+fn is_projected_out(column: &str) -> bool {
+    PROJECTION_MASK.with(|m| m.borrow().contains(&column))
+}
+
+// rendered_by_type returns rendered parts of generic select. Statics in generic functions are
+// shared by all instances, so rendered parts are kept by type.
+pub fn rendered_by_type<T: 'static>(init: impl FnOnce() -> Rendered) -> &'static Rendered {
+    static RENDERED: OnceLock<Mutex<HashMap<TypeId, &'static Rendered>>> = OnceLock::new();
+    let cache = RENDERED.get_or_init(Default::default);
+
+    if let Some(rendered) = cache.lock().unwrap().get(&TypeId::of::<T>()) {
+        return rendered;
+    }
+
+    // nested selects are rendered by init, so lock cannot be held
+    let rendered = init();
+    cache
+        .lock()
+        .unwrap()
+        .entry(TypeId::of::<T>())
+        .or_insert_with(|| Box::leak(Box::new(rendered)))
 }
 
-// implement Query for Vec<Query>
-// @TODO: change to static methods
-impl<T> Select for Vec<T>
+// Grouping is implemented by types that choose grouping key at runtime
+pub trait Grouping {
+    // requested grouping key, None means static group of select
+    fn group_key(&self) -> Option<&str>;
+}
+
+impl Grouping for String {
+    fn group_key(&self) -> Option<&str> {
+        let key = self.trim();
+        if key.is_empty() {
+            None
+        } else {
+            Some(key)
+        }
+    }
+}
+
+impl Grouping for &str {
+    fn group_key(&self) -> Option<&str> {
+        let key = self.trim();
+        if key.is_empty() {
+            None
+        } else {
+            Some(key)
+        }
+    }
+}
+
+impl<T> Grouping for Option<T>
 where
-    T: Select + Default,
+    T: Grouping,
 {
-    fn get_fields<DB: Database>(&self) -> &'static [&'static str] {
-        T::default().get_fields::<DB>()
+    fn group_key(&self) -> Option<&str> {
+        self.as_ref().and_then(|t| t.group_key())
     }
+}
 
-    fn get_fields_str<DB: Database>(&self) -> &'static str {
-        T::default().get_fields_str::<DB>()
+// GroupKey is whitelisted grouping key of select builder
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GroupKey {
+    pub name: &'static str,
+    pub expr: &'static str,
+}
+
+// find_group_key returns grouping key by requested name. Unknown names are errors.
+pub fn find_group_key(
+    keys: &'static [GroupKey],
+    requested: Option<&str>,
+) -> crate::Result<Option<&'static GroupKey>> {
+    match requested {
+        None => Ok(None),
+        Some(name) => match keys.iter().find(|k| k.name == name) {
+            Some(key) => Ok(Some(key)),
+            None => Err(crate::Error::InvalidGroup(name.to_string())),
+        },
     }
-    fn get_table<DB: Database>(&self) -> &'static str {
-        T::default().get_table::<DB>()
+}
+
+// group_fields renders select columns, where column with given name is selected by expression
+// of grouping key (e.g. `DATE_TRUNC('week', created) AS period`)
+pub fn group_fields(columns: &[Column], name: &str, expr: &'static str) -> crate::Result<Vec<String>> {
+    if !columns.iter().any(|c| c.name == name) {
+        return Err(crate::Error::InvalidField(name.to_string()));
     }
-    fn get_query<DB: Database>(&self) -> &'static str {
-        T::default().get_query::<DB>()
+
+    Ok(columns
+        .iter()
+        .map(|c| {
+            if c.name == name {
+                Column { expr, ..*c }.render()
+            } else {
+                c.render()
+            }
+        })
+        .collect())
+}
+
+// has_field returns whether select declares field with given name, derive checks `select` of
+// grouping keys by it in compile time
+pub const fn has_field(fields: &[&str], name: &str) -> bool {
+    let mut i = 0;
+    while i < fields.len() {
+        if crate::query::str_eq(fields[i], name) {
+            return true;
+        }
+        i += 1;
     }
-    fn get_group<DB: Database>(&mut self) -> Option<&'static str> {
-        T::default().get_group::<DB>()
+    false
+}
+
+// group_by returns GROUP BY clause. Grouping key either extends static group (when `select` is
+// None), or replaces entry of given select field. Other entries of static group are kept.
+pub fn group_by(
+    columns: &[Column],
+    group: &[&str],
+    select: Option<&str>,
+    key: Option<&GroupKey>,
+) -> Option<String> {
+    let key = match key {
+        Some(key) => key,
+        None if group.is_empty() => return None,
+        None => return Some(format!("GROUP BY {}", group.join(", "))),
+    };
+
+    let replaced = select
+        .and_then(|name| columns.iter().find(|c| c.name == name))
+        .map(|c| c.group_expr());
+    let mut entries: Vec<&str> = group
+        .iter()
+        .map(|entry| match &replaced {
+            Some(replaced) if entry == replaced => key.expr,
+            _ => entry,
+        })
+        .collect();
+    if !entries.contains(&key.expr) {
+        entries.push(key.expr);
+    }
+    Some(format!("GROUP BY {}", entries.join(", ")))
+}
+
+// FromRowPrefixed decodes row type from columns with given prefix (`sqlx::FromRow` uses empty prefix)
+#[cfg(feature = "sqlx")]
+pub trait FromRowPrefixed<'r, R: Row>: Sized {
+    fn from_row_prefixed(row: &'r R, prefix: &str) -> std::result::Result<Self, Error>;
+}
+
+// column_name returns name of column with prefix
+pub fn column_name<'a>(prefix: &str, name: &'a str) -> Cow<'a, str> {
+    if prefix.is_empty() {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(format!("{}{}", prefix, name))
+    }
+}
+
+// get_column decodes value of column (NULL is decoded into Option as None)
+#[cfg(feature = "sqlx")]
+pub fn get_column<'r, R, T>(row: &'r R, prefix: &str, name: &str) -> std::result::Result<T, Error>
+where
+    R: Row,
+    for<'a> &'a str: ColumnIndex<R>,
+    T: Decode<'r, R::Database> + Type<R::Database>,
+{
+    row.try_get(&*column_name(prefix, name))
+}
+
+// get_column_or_default decodes value of column, when column is not selected by projection
+// default value is returned. Missing column which is not removed by projection is an error.
+#[cfg(feature = "sqlx")]
+pub fn get_column_or_default<'r, R, T>(
+    row: &'r R,
+    prefix: &str,
+    name: &str,
+) -> std::result::Result<T, Error>
+where
+    R: Row,
+    for<'a> &'a str: ColumnIndex<R>,
+    T: Decode<'r, R::Database> + Type<R::Database> + Default,
+{
+    match get_column(row, prefix, name) {
+        Err(Error::ColumnNotFound(_)) if is_projected_out(&column_name(prefix, name)) => {
+            Ok(T::default())
+        }
+        result => result,
+    }
+}
+
+// Rows is implemented by select builders for row type of their select.
+// It is generic parameter (and not associated type), so public builders can have private rows.
+pub trait Rows<T> {}
+
+// Combinator combines queries of multiple select builders
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Combinator {
+    Union,
+    UnionAll,
+    Intersect,
+}
+
+impl Combinator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Union => "UNION",
+            Self::UnionAll => "UNION ALL",
+            Self::Intersect => "INTERSECT",
+        }
+    }
+}
+
+// Part is single select builder in compound query, rendered for given database
+trait Part<DB: Dialect>: Send {
+    fn to_raw_sql(&mut self) -> crate::Result<(String, Vec<Value>)>;
+}
+
+impl<DB, B> Part<DB> for B
+where
+    DB: Dialect,
+    B: SelectBuilder + Send,
+{
+    fn to_raw_sql(&mut self) -> crate::Result<(String, Vec<Value>)> {
+        SelectBuilder::to_raw_sql::<DB>(self)
+    }
+}
+
+// Compound combines select builders with the same row type by UNION, UNION ALL or INTERSECT.
+// Values of all builders are kept in order of their placeholders.
+pub struct Compound<'a, DB: Dialect, T> {
+    first: Box<dyn Part<DB> + 'a>,
+    rest: Vec<(Combinator, Box<dyn Part<DB> + 'a>)>,
+    sorts: Vec<String>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    row: PhantomData<T>,
+}
+
+impl<'a, DB: Dialect, T> Compound<'a, DB, T> {
+    // new creates compound query starting with given builder
+    pub fn new<B>(builder: B) -> Self
+    where
+        B: SelectBuilder + Rows<T> + Send + 'a,
+    {
+        Self {
+            first: Box::new(builder),
+            rest: vec![],
+            sorts: vec![],
+            limit: None,
+            offset: None,
+            row: PhantomData,
+        }
+    }
+
+    // combine adds builder with given combinator
+    pub fn combine<B>(mut self, combinator: Combinator, builder: B) -> Self
+    where
+        B: SelectBuilder + Rows<T> + Send + 'a,
+    {
+        self.rest.push((combinator, Box::new(builder)));
+        self
+    }
+
+    pub fn union<B>(self, builder: B) -> Self
+    where
+        B: SelectBuilder + Rows<T> + Send + 'a,
+    {
+        self.combine(Combinator::Union, builder)
+    }
+
+    pub fn union_all<B>(self, builder: B) -> Self
+    where
+        B: SelectBuilder + Rows<T> + Send + 'a,
+    {
+        self.combine(Combinator::UnionAll, builder)
+    }
+
+    pub fn intersect<B>(self, builder: B) -> Self
+    where
+        B: SelectBuilder + Rows<T> + Send + 'a,
+    {
+        self.combine(Combinator::Intersect, builder)
+    }
+
+    // sort adds outer ORDER BY (ident is column name of result)
+    pub fn sort(mut self, ident: &str, sort: Sort) -> Self {
+        if let Some(clause) = sort.sort::<DB>(ident) {
+            self.sorts.push(clause);
+        }
+        self
+    }
+
+    // limit sets outer LIMIT
+    pub fn limit<L: Limit>(mut self, limit: L) -> Self {
+        self.limit = limit.get_limit();
+        self
+    }
+
+    // offset sets outer OFFSET (only used along with limit)
+    pub fn offset<O: Offset>(mut self, offset: O) -> Self {
+        self.offset = offset.get_offset();
+        self
+    }
+
+    // to_sql returns compound query along with values of all builders
+    pub fn to_sql(&mut self) -> crate::Result<(String, Vec<Value>)> {
+        let (query, mut values) = self.first.to_raw_sql()?;
+        let mut parts: Vec<String> = vec![DB::compound_part(&query)];
+
+        for (combinator, builder) in self.rest.iter_mut() {
+            let (query, builder_values) = builder.to_raw_sql()?;
+            parts.push(combinator.as_str().to_string());
+            parts.push(DB::compound_part(&query));
+            values.extend(builder_values);
+        }
+
+        if !self.sorts.is_empty() {
+            parts.push(format!("ORDER BY {}", self.sorts.join(", ")));
+        }
+
+        if let Some(limit) = self.limit {
+            parts.push(DB::limit_offset(limit, self.offset, !self.sorts.is_empty()));
+        }
+
+        Ok((dialect::number::<DB>(&parts.join(" ")), values))
+    }
+
+    // fetch_all executes compound query (by pool, connection or transaction) and returns all rows
+    #[cfg(feature = "sqlx")]
+    pub async fn fetch_all<'c, E>(&mut self, executor: E) -> crate::Result<Vec<T>>
+    where
+        DB: crate::execute::Backend,
+        for<'q> <DB as HasArguments<'q>>::Arguments: IntoArguments<'q, DB>,
+        E: Executor<'c, Database = DB>,
+        T: for<'r> FromRow<'r, DB::Row> + Send + Unpin,
+    {
+        let (query, values) = self.to_sql()?;
+        let arguments = DB::arguments(values)?;
+        Ok(
+            sqlx::query_as_with::<DB, T, _>(&query, arguments)
+                .fetch_all(executor)
+                .await?,
+        )
     }
 }
@@ -1,30 +1,24 @@
 #![warn(missing_debug_implementations)]
 #![allow(unused_imports)]
 
-use sqlx::Database;
+use crate::dialect::Dialect;
 use std::fmt::Debug;
 
 pub trait Sorter {
-    fn sort<DB: Database>(&self, ident: &str) -> Option<String>;
+    fn sort<DB: Dialect>(&self, ident: &str) -> Option<String>;
 }
 
 // direction in which to go
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub enum Sort {
+    #[default]
     Asc,
     Desc,
 }
 
-// provide defaults
-impl Default for Sort {
-    fn default() -> Self {
-        Self::Asc
-    }
-}
-
 // implement for sort
 impl Sorter for Sort {
-    fn sort<DB: Database>(&self, ident: &str) -> Option<String> {
+    fn sort<DB: Dialect>(&self, ident: &str) -> Option<String> {
         Some(format!(
             "{} {}",
             ident,
@@ -38,7 +32,7 @@ impl Sorter for Sort {
 
 // implement for option
 impl Sorter for Option<Sort> {
-    fn sort<DB: Database>(&self, ident: &str) -> Option<String> {
+    fn sort<DB: Dialect>(&self, ident: &str) -> Option<String> {
         match self {
             Some(t) => t.sort::<DB>(ident),
             None => None,
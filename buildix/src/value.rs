@@ -0,0 +1,209 @@
+use std::cmp::Ordering;
+
+// Value is neutral argument of query. Queries are rendered along with their values, so they
+// can be executed by any driver (sqlx, tokio-postgres, rusqlite, mysql_async, ...).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    // NULL knows type of value, since databases check types of parameters
+    Null(Type),
+    Bool(bool),
+    I32(i32),
+    I64(i64),
+    F64(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    #[cfg(feature = "chrono")]
+    Timestamp(chrono::DateTime<chrono::Utc>),
+    #[cfg(feature = "uuid")]
+    Uuid(uuid::Uuid),
+}
+
+// Type of value
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Type {
+    Bool,
+    I32,
+    I64,
+    F64,
+    String,
+    Bytes,
+    #[cfg(feature = "chrono")]
+    Timestamp,
+    #[cfg(feature = "uuid")]
+    Uuid,
+}
+
+impl Type {
+    // name returns name of type (as reported by errors)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Type::Bool => "bool",
+            Type::I32 => "i32",
+            Type::I64 => "i64",
+            Type::F64 => "f64",
+            Type::String => "string",
+            Type::Bytes => "bytes",
+            #[cfg(feature = "chrono")]
+            Type::Timestamp => "timestamp",
+            #[cfg(feature = "uuid")]
+            Type::Uuid => "uuid",
+        }
+    }
+}
+
+impl Value {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null(_))
+    }
+
+    // get_type returns type of value (NULL has type of its column)
+    pub fn get_type(&self) -> Type {
+        match self {
+            Value::Null(ty) => *ty,
+            Value::Bool(_) => Type::Bool,
+            Value::I32(_) => Type::I32,
+            Value::I64(_) => Type::I64,
+            Value::F64(_) => Type::F64,
+            Value::String(_) => Type::String,
+            Value::Bytes(_) => Type::Bytes,
+            #[cfg(feature = "chrono")]
+            Value::Timestamp(_) => Type::Timestamp,
+            #[cfg(feature = "uuid")]
+            Value::Uuid(_) => Type::Uuid,
+        }
+    }
+
+    // matches compares values the way database does (integers of different sizes are equal,
+    // NULL never matches)
+    pub fn matches(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Null(_), _) | (_, Value::Null(_)) => false,
+            (Value::I32(a), Value::I64(b)) | (Value::I64(b), Value::I32(a)) => i64::from(*a) == *b,
+            (a, b) => a == b,
+        }
+    }
+
+    // compare orders values of the same kind (integers of different sizes are comparable), None
+    // is returned for NULL and values of different kinds
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => a.partial_cmp(b),
+            (Value::I32(a), Value::I32(b)) => a.partial_cmp(b),
+            (Value::I64(a), Value::I64(b)) => a.partial_cmp(b),
+            (Value::I32(a), Value::I64(b)) => i64::from(*a).partial_cmp(b),
+            (Value::I64(a), Value::I32(b)) => a.partial_cmp(&i64::from(*b)),
+            (Value::F64(a), Value::F64(b)) => a.partial_cmp(b),
+            (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+            (Value::Bytes(a), Value::Bytes(b)) => a.partial_cmp(b),
+            #[cfg(feature = "chrono")]
+            (Value::Timestamp(a), Value::Timestamp(b)) => a.partial_cmp(b),
+            #[cfg(feature = "uuid")]
+            (Value::Uuid(a), Value::Uuid(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+// ToValue is implemented by filter values that are bound to query
+pub trait ToValue {
+    // type of value, None of `Option<T>` is bound as NULL of it
+    const TYPE: Type;
+
+    fn to_value(&self) -> Value;
+}
+
+macro_rules! to_value_impl {
+    ($T:ty, $V:ident) => {
+        impl ToValue for $T {
+            const TYPE: Type = Type::$V;
+
+            fn to_value(&self) -> Value {
+                Value::$V(self.clone())
+            }
+        }
+
+        impl From<$T> for Value {
+            fn from(value: $T) -> Self {
+                Value::$V(value)
+            }
+        }
+    };
+}
+
+to_value_impl!(bool, Bool);
+to_value_impl!(i32, I32);
+to_value_impl!(i64, I64);
+to_value_impl!(f64, F64);
+to_value_impl!(String, String);
+to_value_impl!(Vec<u8>, Bytes);
+#[cfg(feature = "chrono")]
+to_value_impl!(chrono::DateTime<chrono::Utc>, Timestamp);
+#[cfg(feature = "uuid")]
+to_value_impl!(uuid::Uuid, Uuid);
+
+impl ToValue for str {
+    const TYPE: Type = Type::String;
+
+    fn to_value(&self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl<T> ToValue for &T
+where
+    T: ToValue + ?Sized,
+{
+    const TYPE: Type = T::TYPE;
+
+    fn to_value(&self) -> Value {
+        (**self).to_value()
+    }
+}
+
+// ValueOf returns value of select field for in-memory filtering, fields of types which are not
+// convertible to value have no value (autoref specialization, used by generated code)
+#[doc(hidden)]
+pub struct ValueOf<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait ToSomeValue {
+    fn value_of(&self) -> Option<Value>;
+}
+
+impl<'a, T: ToValue + ?Sized> ToSomeValue for ValueOf<'a, T> {
+    fn value_of(&self) -> Option<Value> {
+        Some(self.0.to_value())
+    }
+}
+
+#[doc(hidden)]
+pub trait ToNoValue {
+    fn value_of(&self) -> Option<Value>;
+}
+
+impl<'a, T: ?Sized> ToNoValue for &ValueOf<'a, T> {
+    fn value_of(&self) -> Option<Value> {
+        None
+    }
+}
+
+// None is bound as NULL
+impl<T> ToValue for Option<T>
+where
+    T: ToValue,
+{
+    const TYPE: Type = T::TYPE;
+
+    fn to_value(&self) -> Value {
+        match self {
+            Some(value) => value.to_value(),
+            None => Value::Null(T::TYPE),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+CREATE TABLE "user" (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    email TEXT,
+    age INTEGER
+);
+
+CREATE TABLE "order" (
+    id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    price INTEGER NOT NULL,
+    note TEXT,
+    PRIMARY KEY (id)
+);
@@ -0,0 +1,91 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use buildix_derive::{Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::Postgres;
+
+#[test]
+fn test_aggregate() {
+    let mut query = UserOrdersBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT u.name, COUNT(*) AS order_count, SUM(o.price) AS total, MAX(o.created) AS last_order, LOWER(u.email) AS email FROM user AS u, INNER JOIN order o (o.user_id = u.id) GROUP BY u.name, LOWER(u.email)"#
+    );
+
+    query.filter.name = Some("peter".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT u.name, COUNT(*) AS order_count, SUM(o.price) AS total, MAX(o.created) AS last_order, LOWER(u.email) AS email FROM user AS u, INNER JOIN order o (o.user_id = u.id) WHERE u.name = $1 GROUP BY u.name, LOWER(u.email)"#
+    );
+}
+
+#[test]
+fn test_aggregate_raw_group() {
+    let mut query = TotalBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT status, AVG(total) AS total FROM order GROUP BY status"#
+    );
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserOrdersBuilder {
+    #[buildix(select)]
+    select: Vec<UserOrders>,
+
+    #[buildix(filter)]
+    filter: UserOrdersFilter,
+}
+
+#[derive(Default, Filter)]
+struct UserOrdersFilter {
+    #[buildix(expr = "u.name = ?")]
+    name: Option<String>,
+}
+
+// group entries reference select fields
+#[derive(Default, Select)]
+#[buildix(from(table(name = "user", alias = "u")))]
+#[buildix(from(join(name = "order", alias = "o", on = "o.user_id = u.id")))]
+#[buildix(group = "name", group = "email")]
+struct UserOrders {
+    #[buildix(table = "u")]
+    name: String,
+
+    #[buildix(aggregate = "count")]
+    order_count: i64,
+
+    #[buildix(aggregate = "sum", table = "o", column = "price")]
+    total: i64,
+
+    #[buildix(aggregate = "MAX", table = "o", column = "created")]
+    last_order: i64,
+
+    #[buildix(expr = "LOWER(u.email)")]
+    email: String,
+}
+
+#[derive(Default, SelectBuilder)]
+struct TotalBuilder {
+    #[buildix(select)]
+    select: Vec<OrderTotal>,
+}
+
+// raw group entries are matched with field expressions
+#[derive(Default, Select)]
+#[buildix(table = "order", group = "status")]
+struct OrderTotal {
+    status: i32,
+
+    #[buildix(aggregate = "avg")]
+    total: f64,
+}
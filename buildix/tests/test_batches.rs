@@ -0,0 +1,157 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use buildix::execute::{Batch, Execute};
+use buildix_derive::{DeleteBuilder, Filter};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::sqlite::{SqliteConnection, SqlitePool, SqlitePoolOptions};
+use sqlx::{Connection, Executor};
+
+async fn connect() -> SqliteConnection {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    conn.execute("CREATE TABLE log (id INTEGER NOT NULL, level TEXT NOT NULL)")
+        .await
+        .unwrap();
+    for id in 1..=7 {
+        sqlx::query("INSERT INTO log (id, level) VALUES (?, ?)")
+            .bind(id)
+            .bind(if id % 2 == 0 { "debug" } else { "info" })
+            .execute(&mut conn)
+            .await
+            .unwrap();
+    }
+    conn
+}
+
+async fn remaining(conn: &mut SqliteConnection) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM log")
+        .fetch_one(conn)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_batches() {
+    let mut conn = connect().await;
+
+    let mut delete = DeleteLog::default();
+    delete.all = true;
+    let total = delete.execute_in_batches(&mut conn, 3).await.unwrap();
+    assert_eq!(total, 7);
+    assert_eq!(delete.count, 7);
+    assert_eq!(remaining(&mut conn).await, 0);
+}
+
+#[tokio::test]
+async fn test_batches_pool() {
+    // in-memory database lives only as long as single connection of pool
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    pool.execute("CREATE TABLE log (id INTEGER NOT NULL, level TEXT NOT NULL)")
+        .await
+        .unwrap();
+    for id in 1..=7 {
+        sqlx::query("INSERT INTO log (id, level) VALUES (?, ?)")
+            .bind(id)
+            .bind(if id % 2 == 0 { "debug" } else { "info" })
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let mut delete = DeleteLog::default();
+    delete.level = Some("info".to_string());
+    let total = delete.execute_in_batches_pool(&pool, 3).await.unwrap();
+    assert_eq!(total, 4);
+    assert_eq!(delete.count, 4);
+
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM log")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 3);
+}
+
+#[tokio::test]
+async fn test_batches_filter_limit() {
+    let mut conn = connect().await;
+    let mut tx = conn.begin().await.unwrap();
+
+    // limit of builder caps total number of deleted rows
+    let mut delete = DeleteLog::default();
+    delete.level = Some("info".to_string());
+    delete.limit = Some(3);
+    let batch = Batch::new(2).pause(Duration::from_millis(1));
+    let total = delete.execute_in_batches(&mut tx, batch).await.unwrap();
+    assert_eq!(total, 3);
+    assert_eq!(delete.count, 3);
+    tx.commit().await.unwrap();
+
+    // 4 debug rows and 1 info row
+    assert_eq!(remaining(&mut conn).await, 4);
+}
+
+#[tokio::test]
+async fn test_batches_cancel() {
+    let mut conn = connect().await;
+
+    // cancel after two batches
+    let batches = Arc::new(AtomicUsize::new(0));
+    let counter = batches.clone();
+    let batch = Batch::new(2).cancel(move || counter.fetch_add(1, Ordering::SeqCst) >= 2);
+
+    let mut delete = DeleteLog::default();
+    delete.all = true;
+    let total = delete.execute_in_batches(&mut conn, batch).await.unwrap();
+    assert_eq!(total, 4);
+    assert_eq!(remaining(&mut conn).await, 3);
+}
+
+#[tokio::test]
+async fn test_batches_full_table() {
+    let mut conn = connect().await;
+    let mut delete = DeleteLog::default();
+    let err = delete.execute_in_batches(&mut conn, 2).await.unwrap_err();
+    assert!(matches!(err, buildix::Error::FullTable(_)));
+}
+
+#[tokio::test]
+async fn test_batches_zero_size() {
+    let mut conn = connect().await;
+    let mut delete = DeleteLog::default();
+    delete.level = Some("debug".to_string());
+    let err = delete
+        .execute_in_batches(&mut conn, Batch::new(0))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, buildix::Error::InvalidBatchSize(0)));
+    assert_eq!(remaining(&mut conn).await, 7);
+}
+
+#[derive(Default, DeleteBuilder)]
+#[buildix(table = "log")]
+struct DeleteLog {
+    #[buildix(filter)]
+    level: Option<String>,
+
+    #[buildix(limit)]
+    limit: Option<i64>,
+
+    #[buildix(count)]
+    count: i64,
+
+    #[buildix(allow_full_table)]
+    all: bool,
+}
@@ -0,0 +1,9 @@
+// derives report invalid attributes, sql fragments and schema mismatches as compile errors
+#[test]
+fn test_compile_fail() {
+    // ui tests are compiled in other crate, so schema is referenced by absolute path
+    std::env::set_var("BUILDIX_TESTS_DIR", concat!(env!("CARGO_MANIFEST_DIR"), "/tests"));
+
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,130 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use buildix::select::{Combinator, Compound};
+use buildix::sort::Sort;
+use buildix_derive::{Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Executor, Postgres, Sqlite};
+
+#[test]
+fn test_union_all() {
+    let mut active = ActiveUserBuilder::default();
+    active.filter.name = Some("peter".to_string());
+
+    let mut query =
+        Compound::<Postgres, SelectUser>::new(active).union_all(ArchivedUserBuilder::default());
+    let (q, _) = query.to_sql().unwrap();
+
+    assert_eq!(
+        q,
+        r#"(SELECT id, name FROM user WHERE (archived = FALSE AND name = $1)) UNION ALL (SELECT id, name FROM user WHERE archived = TRUE LIMIT 10)"#
+    );
+}
+
+#[test]
+fn test_compound_sort_limit() {
+    let mut query = Compound::<Postgres, SelectUser>::new(ActiveUserBuilder::default())
+        .union(ArchivedUserBuilder::default())
+        .combine(Combinator::Intersect, ActiveUserBuilder::default())
+        .sort("name", Sort::Desc)
+        .sort("id", Sort::Asc)
+        .limit(20)
+        .offset(40);
+    let (q, _) = query.to_sql().unwrap();
+
+    assert_eq!(
+        q,
+        r#"(SELECT id, name FROM user WHERE archived = FALSE) UNION (SELECT id, name FROM user WHERE archived = TRUE LIMIT 10) INTERSECT (SELECT id, name FROM user WHERE archived = FALSE) ORDER BY name DESC, id ASC LIMIT 20 OFFSET 40"#
+    );
+}
+
+#[tokio::test]
+async fn test_compound_sqlite() {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    conn.execute(
+        "CREATE TABLE user (id INTEGER NOT NULL, name TEXT NOT NULL, archived BOOLEAN NOT NULL)",
+    )
+    .await
+    .unwrap();
+    conn.execute(
+        "INSERT INTO user (id, name, archived) VALUES (1, 'peter', false), (2, 'john', false), (3, 'paul', true), (4, 'george', true)",
+    )
+    .await
+    .unwrap();
+
+    let mut active = ActiveUserBuilder::default();
+    active.filter.name = Some("peter".to_string());
+
+    // sqlite does not allow parenthesized queries in compound query
+    let mut query = Compound::<Sqlite, SelectUser>::new(active)
+        .union_all(ArchivedUserBuilder::default())
+        .sort("name", Sort::Asc)
+        .limit(2)
+        .offset(1);
+    let (q, _) = query.to_sql().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT * FROM (SELECT id, name FROM user WHERE (archived = FALSE AND name = ?)) UNION ALL SELECT * FROM (SELECT id, name FROM user WHERE archived = TRUE LIMIT 10) ORDER BY name ASC LIMIT 2 OFFSET 1"#
+    );
+
+    // values of both builders are bound in order
+    let users = query.fetch_all(&mut conn).await.unwrap();
+    let names: Vec<&str> = users.iter().map(|u| u.name.as_str()).collect();
+    assert_eq!(names, vec!["paul", "peter"]);
+}
+
+// compound query is executed into rows of its builders
+async fn fetch_users(pool: &sqlx::PgPool) -> buildix::Result<Vec<SelectUser>> {
+    Compound::<Postgres, SelectUser>::new(ActiveUserBuilder::default())
+        .union_all(ArchivedUserBuilder::default())
+        .fetch_all(pool)
+        .await
+}
+
+#[derive(Default, SelectBuilder)]
+struct ActiveUserBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    filter: ActiveFilter,
+}
+
+#[derive(Default, Filter)]
+struct ActiveFilter {
+    archived: bool,
+    name: Option<String>,
+}
+
+#[derive(SelectBuilder)]
+struct ArchivedUserBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    archived: bool,
+
+    #[buildix(limit)]
+    limit: i32,
+}
+
+impl Default for ArchivedUserBuilder {
+    fn default() -> Self {
+        Self {
+            select: vec![],
+            archived: true,
+            limit: 10,
+        }
+    }
+}
+
+#[derive(Default, Select)]
+#[buildix(from(table(name = "user")))]
+struct SelectUser {
+    id: i32,
+    name: String,
+}
@@ -1,24 +1,85 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
 
+use buildix::execute::Execute;
 use buildix::sort::Sort;
 use buildix_derive::{DeleteBuilder, Filter};
 
 #[allow(unused_imports)]
 use buildix::prelude::*;
-use sqlx::Postgres;
+use sqlx::sqlite::SqliteConnection;
+use buildix::dialect::MySql;
+use sqlx::{Connection, Executor, Postgres, Sqlite};
 use thiserror::Error;
 
 #[test]
 fn test_delete() {
     let mut query = TestDeleteBuilder::default();
     let (q, _) = query.to_sql::<Postgres>().unwrap();
-    assert_eq!(q, "DELETE FROM user WHERE id = ?");
+    assert_eq!(q, "DELETE FROM user WHERE id = $1");
 
     query.limit = Some(42);
     let (q, _) = query.to_sql::<Postgres>().unwrap();
 
-    assert_eq!(q, "DELETE FROM user WHERE id = ? LIMIT 42");
+    assert_eq!(
+        q,
+        "DELETE FROM user WHERE ctid IN (SELECT ctid FROM user WHERE id = $1 LIMIT 42)"
+    );
+
+    let (q, _) = query.to_sql::<Sqlite>().unwrap();
+
+    assert_eq!(
+        q,
+        "DELETE FROM user WHERE rowid IN (SELECT rowid FROM user WHERE id = ? LIMIT 42)"
+    );
+}
+
+#[test]
+fn test_delete_returning() {
+    let mut query = ReturningDeleteBuilder::default();
+    query.id = vec![1, 2];
+
+    let (q, _) = query.to_returning_sql::<Postgres>().unwrap();
+    assert_eq!(q, "DELETE FROM user WHERE id IN ($1, $2) RETURNING id, name");
+
+    // all columns are returned by default
+    let (q, _) = TestDeleteBuilder::default().to_returning_sql::<Sqlite>().unwrap();
+    assert_eq!(q, "DELETE FROM user WHERE id = ? RETURNING *");
+
+    assert!(matches!(
+        query.to_returning_sql::<MySql>(),
+        Err(buildix::Error::Unsupported("RETURNING", "mysql"))
+    ));
+}
+
+#[tokio::test]
+async fn test_execute_returning() {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    conn.execute("CREATE TABLE user (id INTEGER NOT NULL, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+    conn.execute("INSERT INTO user (id, name) VALUES (1, 'peter'), (2, 'john'), (3, 'paul')")
+        .await
+        .unwrap();
+
+    let mut query = ReturningDeleteBuilder::default();
+    query.id = vec![1, 3];
+
+    let mut deleted: Vec<(i64, String)> = query.execute_returning(&mut conn).await.unwrap();
+    deleted.sort();
+    assert_eq!(deleted, vec![(1, "peter".to_string()), (3, "paul".to_string())]);
+    assert_eq!(query.count, 2);
+}
+
+#[derive(Default, DeleteBuilder)]
+#[buildix(table = "user", returning = "id, name")]
+pub struct ReturningDeleteBuilder {
+    #[buildix(filter)]
+    id: Vec<i64>,
+
+    #[buildix(count)]
+    count: i64,
 }
 
 #[derive(Default, DeleteBuilder)]
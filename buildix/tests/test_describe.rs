@@ -0,0 +1,156 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use buildix::describe::{Describe, Param, Schema};
+use buildix::filter::fields::{IsNull, Ops};
+use buildix::sort::Sort;
+use buildix_derive::{Filter, Select, SelectBuilder};
+use serde_json::json;
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+
+#[test]
+fn test_describe_filter() {
+    let params = UserFilter::params("");
+    let names: Vec<&str> = params.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["id", "age", "deleted", "either.email"]);
+
+    assert_eq!(params[0].schema, Schema::Array(Box::new(Schema::Integer("int64"))));
+    assert_eq!(params[1].ops, vec!["gt", "lte"]);
+    assert_eq!(params[1].description, Some("age of user in years".to_string()));
+
+    assert_eq!(
+        UserFilter::json_schema(),
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "array", "items": {"type": "integer", "format": "int64"}},
+                "id__in": {"type": "array", "items": {"type": "integer", "format": "int64"}},
+                "age": {"type": "integer", "format": "int32", "description": "age of user in years"},
+                "age__gt": {"type": "integer", "format": "int32", "description": "age of user in years"},
+                "age__lte": {"type": "integer", "format": "int32", "description": "age of user in years"},
+                "deleted": {"type": "boolean"},
+                "either.email": {"type": "string"},
+            },
+            "additionalProperties": false,
+        })
+    );
+}
+
+#[test]
+fn test_describe_supported_ops() {
+    // operators not supported by type are not accepted by query string, so not described
+    let params = NameFilter::params("");
+    assert_eq!(params[0].ops, vec!["in"]);
+    assert_eq!(params[1].ops, vec!["isnull"]);
+}
+
+#[test]
+fn test_describe_builder() {
+    let params = UserQuery::openapi();
+    let names: Vec<&str> = params
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(
+        names,
+        vec![
+            "id",
+            "id__in",
+            "age",
+            "age__gt",
+            "age__lte",
+            "deleted",
+            "either.email",
+            "sort_age",
+            "limit",
+            "offset"
+        ]
+    );
+
+    assert_eq!(
+        params[0],
+        json!({
+            "name": "id",
+            "in": "query",
+            "required": false,
+            "schema": {"type": "array", "items": {"type": "integer", "format": "int64"}},
+            "style": "form",
+            "explode": false,
+        })
+    );
+    assert_eq!(
+        params[7],
+        json!({
+            "name": "sort_age",
+            "in": "query",
+            "required": false,
+            "description": "sort by `age`",
+            "schema": {"type": "string", "enum": ["asc", "desc"]},
+        })
+    );
+    assert_eq!(
+        params[8]["schema"],
+        json!({"type": "integer", "format": "int64", "minimum": 0})
+    );
+}
+
+#[derive(Debug, Default, Filter)]
+#[buildix(describe)]
+struct UserFilter {
+    #[buildix(ops = "in")]
+    id: Vec<i64>,
+
+    /// age of user in years
+    #[buildix(ops = "gt, lte")]
+    age: Ops<i32>,
+
+    deleted: Option<IsNull>,
+
+    either: EitherFilter,
+}
+
+#[derive(Debug, Default, Filter)]
+#[buildix(describe)]
+struct NameFilter {
+    #[buildix(ops = "in, gt")]
+    id: Vec<i64>,
+
+    #[buildix(ops = "isnull, icontains")]
+    deleted: Option<IsNull>,
+}
+
+#[derive(Debug, Default, Filter)]
+#[buildix(describe, operator = "OR")]
+struct EitherFilter {
+    email: Option<String>,
+}
+
+#[derive(Default, SelectBuilder)]
+#[buildix(describe)]
+struct UserQuery {
+    #[buildix(select)]
+    select: User,
+
+    #[buildix(filter)]
+    filter: UserFilter,
+
+    #[buildix(sort = "age")]
+    sort_age: Option<Sort>,
+
+    #[buildix(limit)]
+    limit: Option<i64>,
+
+    #[buildix(offset)]
+    offset: Option<i64>,
+}
+
+#[derive(Default, Select)]
+#[buildix(table = "user")]
+struct User {
+    id: i64,
+}
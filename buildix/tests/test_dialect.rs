@@ -0,0 +1,147 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix::dialect::{self, Dialect};
+use buildix_derive::{DeleteBuilder, Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::{Postgres, Sqlite};
+
+#[test]
+fn test_placeholders() {
+    let mut query = UserBuilder::default();
+    query.filter.name = Some("peter".to_string());
+    query.filter.ids = vec![1, 2];
+
+    let (q, _) = query.to_sql::<Sqlite>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id FROM user WHERE (name = ? AND status <> '?' AND ids IN (?, ?))"#
+    );
+
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id FROM user WHERE (name = $1 AND status <> '?' AND ids IN ($2, $3))"#
+    );
+
+    let (q, _) = query.to_sql::<Custom>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id FROM user WHERE (name = :1 AND status <> '?' AND ids IN (:2, :3))"#
+    );
+
+    // raw query keeps `?` placeholders
+    let (q, _) = query.to_raw_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id FROM user WHERE (name = ? AND status <> '?' AND ids IN (?, ?))"#
+    );
+}
+
+#[test]
+fn test_custom_dialect() {
+    let mut query = UserBuilder::default();
+    query.limit = Some(10);
+    query.offset = Some(20);
+    query.filter.deleted = Some(true.into());
+
+    let (q, _) = query.to_sql::<Custom>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id FROM user WHERE deleted IS NULL FETCH FIRST 10 ROWS SKIP 20"#
+    );
+
+    let mut query = DeleteUser { id: 1, limit: 5 };
+    let (q, _) = query.to_sql::<Custom>().unwrap();
+    assert_eq!(q, r#"DELETE FROM user WHERE id = :1 LIMIT 5"#);
+}
+
+#[test]
+fn test_mssql_limit_offset() {
+    let mut query = UserBuilder::default();
+    query.limit = Some(10);
+    query.offset = Some(20);
+
+    // OFFSET ... FETCH needs ORDER BY
+    let (q, _) = query.to_sql::<dialect::Mssql>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id FROM user ORDER BY (SELECT NULL) OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"#
+    );
+    assert_eq!(
+        dialect::Mssql::limit_offset(10, None, true),
+        "OFFSET 0 ROWS FETCH NEXT 10 ROWS ONLY"
+    );
+}
+
+#[test]
+fn test_number() {
+    assert_eq!(
+        dialect::number::<Postgres>(r#"a = ? AND "we?rd" = ? AND b = 'it''s ?'"#),
+        r#"a = $1 AND "we?rd" = $2 AND b = 'it''s ?'"#
+    );
+    assert_eq!(Custom::quote("user"), r#""user""#);
+    assert_eq!(Custom::boolean(true), "TRUE");
+}
+
+// Custom is dialect of database not supported by sqlx
+struct Custom;
+
+impl Dialect for Custom {
+    const NAME: &'static str = "custom";
+
+    fn placeholder(n: usize) -> String {
+        format!(":{}", n)
+    }
+
+    fn limit_offset(limit: u64, offset: Option<u64>, _ordered: bool) -> String {
+        match offset {
+            Some(offset) => format!("FETCH FIRST {} ROWS SKIP {}", limit, offset),
+            None => format!("FETCH FIRST {} ROWS", limit),
+        }
+    }
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    filter: UserFilter,
+
+    #[buildix(limit)]
+    limit: Option<i32>,
+
+    #[buildix(offset)]
+    offset: Option<i32>,
+}
+
+#[derive(Default, Filter)]
+struct UserFilter {
+    #[buildix(expr = "name = ? AND status <> '?'")]
+    name: Option<String>,
+
+    ids: Vec<i32>,
+
+    deleted: Option<buildix::filter::fields::IsNull>,
+}
+
+#[derive(Default, Select)]
+#[buildix(table = "user")]
+struct SelectUser {
+    id: i32,
+}
+
+#[derive(DeleteBuilder)]
+#[buildix(table = "user")]
+struct DeleteUser {
+    #[buildix(filter)]
+    id: i32,
+
+    #[buildix(limit)]
+    limit: i32,
+}
@@ -0,0 +1,109 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix::dialect::{Mssql, MySql, Sqlite};
+use buildix::sort::Sort;
+use buildix_derive::{Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::Postgres;
+
+#[test]
+fn test_distinct() {
+    let mut query = DistinctBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT DISTINCT name, email FROM user"#);
+}
+
+#[test]
+fn test_distinct_field() {
+    let mut query = DistinctFieldBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT name, email FROM user"#);
+
+    query.distinct = true;
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT DISTINCT name, email FROM user"#);
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_distinct_on() {
+    let mut query = DistinctOnBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT DISTINCT ON (email) name, email FROM user ORDER BY email ASC"#
+    );
+
+    query.sort_name = Some(Sort::Desc);
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT DISTINCT ON (email) name, email FROM user ORDER BY email ASC, name DESC"#
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_distinct_on_unsupported() {
+    // other databases do not support DISTINCT ON
+    let mut query = DistinctOnBuilder::default();
+    assert!(matches!(
+        query.to_sql::<MySql>(),
+        Err(buildix::Error::Unsupported("DISTINCT ON", "mysql"))
+    ));
+    assert!(matches!(
+        query.to_sql::<Sqlite>(),
+        Err(buildix::Error::Unsupported("DISTINCT ON", "sqlite"))
+    ));
+    assert!(matches!(
+        query.to_sql::<Mssql>(),
+        Err(buildix::Error::Unsupported("DISTINCT ON", "mssql"))
+    ));
+}
+
+#[derive(Default, SelectBuilder)]
+struct DistinctBuilder {
+    #[buildix(select)]
+    select: Vec<DistinctUser>,
+}
+
+#[derive(Default, SelectBuilder)]
+struct DistinctFieldBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(distinct)]
+    distinct: bool,
+}
+
+#[cfg(feature = "postgres")]
+#[derive(Default, SelectBuilder)]
+#[buildix(distinct_on = "email")]
+struct DistinctOnBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(sort = "email")]
+    sort_email: Sort,
+
+    #[buildix(sort = "name")]
+    sort_name: Option<Sort>,
+}
+
+#[derive(Default, Select)]
+#[buildix(from(table(name = "user")), distinct)]
+struct DistinctUser {
+    name: String,
+    email: String,
+}
+
+#[derive(Default, Select)]
+#[buildix(from(table(name = "user")))]
+struct SelectUser {
+    name: String,
+    email: String,
+}
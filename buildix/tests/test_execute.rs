@@ -0,0 +1,104 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix::execute::{Execute, Fetch};
+use buildix_derive::{DeleteBuilder, Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::sqlite::{Sqlite, SqliteConnection};
+use sqlx::{Connection, Executor};
+
+async fn connect() -> SqliteConnection {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    conn.execute("CREATE TABLE user (id INTEGER NOT NULL, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+    conn.execute("INSERT INTO user (id, name) VALUES (1, 'peter'), (2, 'john'), (3, 'paul')")
+        .await
+        .unwrap();
+    conn
+}
+
+#[tokio::test]
+async fn test_fetch() {
+    let mut conn = connect().await;
+
+    let mut query = UserBuilder::default();
+    query.filter.name = Some("john".to_string());
+    let users = query.fetch_all(&mut conn).await.unwrap();
+    assert_eq!(
+        users,
+        vec![SelectUser {
+            id: 2,
+            name: "john".to_string()
+        }]
+    );
+
+    let user = query.fetch_one(&mut conn).await.unwrap();
+    assert_eq!(user.id, 2);
+
+    query.filter.name = Some("george".to_string());
+    let user = query.fetch_optional(&mut conn).await.unwrap();
+    assert_eq!(user, None);
+}
+
+#[tokio::test]
+async fn test_transaction() {
+    let mut conn = connect().await;
+
+    // delete and select run in single transaction
+    let mut tx = conn.begin().await.unwrap();
+    let mut delete = DeleteUser {
+        id: vec![1, 2],
+        count: 0,
+    };
+    let affected = delete.execute(&mut tx).await.unwrap();
+    assert_eq!(affected, 2);
+    assert_eq!(delete.count, 2);
+
+    let users = UserBuilder::default()
+        .fetch_all(&mut tx)
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 1);
+    tx.rollback().await.unwrap();
+
+    let users = UserBuilder::default()
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(users.len(), 3);
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    filter: UserFilter,
+}
+
+#[derive(Default, Filter)]
+struct UserFilter {
+    name: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Select)]
+#[buildix(table = "user")]
+struct SelectUser {
+    id: i64,
+    name: String,
+}
+
+#[derive(DeleteBuilder)]
+#[buildix(table = "user")]
+struct DeleteUser {
+    #[buildix(filter)]
+    id: Vec<i64>,
+
+    #[buildix(count)]
+    count: i64,
+}
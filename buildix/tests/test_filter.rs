@@ -13,7 +13,7 @@ fn test_filter() {
 
     assert_eq!(
         q,
-        r#"SELECT u.id FROM user AS u WHERE (priority = ? AND age ISNULL)"#
+        r#"SELECT u.id FROM user AS u WHERE (priority = $1 AND age IS NULL)"#
     );
 
     query.filter.author_id = Some(2);
@@ -21,14 +21,14 @@ fn test_filter() {
 
     assert_eq!(
         q,
-        r#"SELECT u.id FROM user AS u WHERE (author_id = ? AND priority = ? AND age ISNULL)"#
+        r#"SELECT u.id FROM user AS u WHERE (author_id = $1 AND priority = $2 AND age IS NULL)"#
     );
 
     query.filter.last_updated = Some(12345);
     let (q, _v) = query.to_sql::<Postgres>().unwrap();
     assert_eq!(
         q,
-        r#"SELECT u.id FROM user AS u WHERE (author_id = ? AND last_updated < ? AND priority = ? AND age ISNULL)"#
+        r#"SELECT u.id FROM user AS u WHERE (author_id = $1 AND last_updated < $2 AND priority = $3 AND age IS NULL)"#
     );
 
     query.filter.something = Some(false.into());
@@ -36,24 +36,39 @@ fn test_filter() {
 
     assert_eq!(
         q,
-        r#"SELECT u.id FROM user AS u WHERE (author_id = ? AND last_updated < ? AND priority = ? AND age ISNULL AND something NOT ISNULL)"#
+        r#"SELECT u.id FROM user AS u WHERE (author_id = $1 AND last_updated < $2 AND priority = $3 AND age IS NULL AND something IS NOT NULL)"#
     );
 
     query.filter.inner.inner_id = Some(42);
     let (q, _v) = query.to_sql::<Postgres>().unwrap();
     assert_eq!(
         q,
-        r#"SELECT u.id FROM user AS u WHERE (author_id = ? AND last_updated < ? AND priority = ? AND age ISNULL AND something NOT ISNULL AND inner_id = ?)"#
+        r#"SELECT u.id FROM user AS u WHERE (author_id = $1 AND last_updated < $2 AND priority = $3 AND age IS NULL AND something IS NOT NULL AND inner_id = $4)"#
     );
 
     query.filter.inner.second = Some(314);
     let (q, _v) = query.to_sql::<Postgres>().unwrap();
     assert_eq!(
         q,
-        r#"SELECT u.id FROM user AS u WHERE (author_id = ? AND last_updated < ? AND priority = ? AND age ISNULL AND something NOT ISNULL AND (inner_id = ? OR second = ?))"#
+        r#"SELECT u.id FROM user AS u WHERE (author_id = $1 AND last_updated < $2 AND priority = $3 AND age IS NULL AND something IS NOT NULL AND (inner_id = $4 OR second = $5))"#
     );
 }
 
+#[test]
+fn test_isnull_tri_state() {
+    let mut query = DeletedQuery::default();
+    let (q, _v) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT u.id FROM user AS u"#);
+
+    query.filter.deleted = Some(true.into());
+    let (q, _v) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT u.id FROM user AS u WHERE deleted IS NULL"#);
+
+    query.filter.deleted = Some(false.into());
+    let (q, _v) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT u.id FROM user AS u WHERE deleted IS NOT NULL"#);
+}
+
 #[derive(Default, SelectBuilder)]
 struct FilterQuery {
     #[buildix(select)]
@@ -84,6 +99,22 @@ struct Filter {
     inner: InnerFilter,
 }
 
+#[derive(Default, SelectBuilder)]
+struct DeletedQuery {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    filter: DeletedFilter,
+}
+
+#[derive(Default, Filter)]
+struct DeletedFilter {
+    // None means either (isnull does not apply to null checks)
+    #[buildix(isnull)]
+    deleted: Option<buildix::filter::fields::IsNull>,
+}
+
 #[derive(Debug, Default, Filter)]
 #[buildix(operator = "OR")]
 struct InnerFilter {
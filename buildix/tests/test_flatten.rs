@@ -0,0 +1,153 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix::Select;
+use buildix_derive::{Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::sqlite::{Sqlite, SqliteConnection};
+use sqlx::{Connection, Postgres};
+
+#[test]
+fn test_flatten() {
+    let mut query = OrderBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT o.id, o.total, u.id AS u_id, u.name AS u_name, UPPER(u.name) AS u_upper_name FROM order AS o, INNER JOIN user u (u.id = o.user_id)"#
+    );
+
+    query.filter.user_name = Some("peter".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT o.id, o.total, u.id AS u_id, u.name AS u_name, UPPER(u.name) AS u_upper_name FROM order AS o, INNER JOIN user u (u.id = o.user_id) WHERE u.name = $1"#
+    );
+}
+
+#[test]
+fn test_flatten_columns() {
+    assert_eq!(
+        SelectOrder::get_field_names(),
+        &["id", "total", "u_id", "u_name", "u_upper_name"]
+    );
+    assert_eq!(
+        SelectOrder::get_columns()[3],
+        buildix::select::Column {
+            name: "u_name",
+            table: "u",
+            column: "name",
+            expr: "",
+        }
+    );
+
+    // default prefix is field name
+    assert_eq!(
+        SelectAuthor::get_fields::<Postgres>(),
+        &[
+            "user.id AS author_id",
+            "user.name AS author_name",
+            "UPPER(u.name) AS author_upper_name"
+        ]
+    );
+}
+
+#[test]
+fn test_flatten_projection() {
+    let mut query = OrderBuilder::default();
+    query.fields = vec!["id".to_string(), "u_name".to_string()];
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT o.id, u.name AS u_name FROM order AS o, INNER JOIN user u (u.id = o.user_id)"#
+    );
+}
+
+#[tokio::test]
+async fn test_flatten_from_row() {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    for query in [
+        "CREATE TABLE user (id INTEGER NOT NULL, name TEXT NOT NULL)",
+        "CREATE TABLE \"order\" (id INTEGER NOT NULL, user_id INTEGER NOT NULL, total INTEGER NOT NULL)",
+        "INSERT INTO user (id, name) VALUES (1, 'peter')",
+        "INSERT INTO \"order\" (id, user_id, total) VALUES (10, 1, 100)",
+    ] {
+        sqlx::query(query).execute(&mut conn).await.unwrap();
+    }
+
+    // flattened columns are decoded into nested struct
+    let q = format!(
+        r#"SELECT {} FROM "order" AS o INNER JOIN user u ON u.id = o.user_id"#,
+        SelectOrder::get_fields_str::<Sqlite>()
+    );
+
+    let orders: Vec<SelectOrder> = sqlx::query_as(&q).fetch_all(&mut conn).await.unwrap();
+
+    assert_eq!(
+        orders,
+        vec![SelectOrder {
+            id: 10,
+            total: 100,
+            user: SelectUser {
+                id: 1,
+                name: "peter".to_string(),
+                upper_name: "PETER".to_string(),
+            },
+        }]
+    );
+}
+
+#[derive(Default, SelectBuilder)]
+struct OrderBuilder {
+    #[buildix(select)]
+    select: Vec<SelectOrder>,
+
+    #[buildix(filter)]
+    filter: OrderFilter,
+
+    #[buildix(fields)]
+    fields: Vec<String>,
+}
+
+#[derive(Default, Filter)]
+struct OrderFilter {
+    #[buildix(expr = "u.name = ?")]
+    user_name: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Select)]
+#[buildix(from(table(name = "order", alias = "o")))]
+#[buildix(from(join(name = "user", alias = "u", on = "u.id = o.user_id")))]
+struct SelectOrder {
+    #[buildix(table = "o")]
+    id: i64,
+
+    #[buildix(table = "o")]
+    total: i64,
+
+    #[buildix(flatten, prefix = "u_", table = "u")]
+    user: SelectUser,
+}
+
+#[derive(Debug, PartialEq, Select)]
+#[buildix(table = "user")]
+struct SelectUser {
+    id: i64,
+
+    name: String,
+
+    #[buildix(expr = "UPPER(u.name)")]
+    upper_name: String,
+}
+
+#[derive(Debug, PartialEq, Select)]
+#[buildix(from(table(name = "book")))]
+struct SelectAuthor {
+    #[buildix(flatten, table = "user")]
+    author: SelectUser,
+}
@@ -0,0 +1,178 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use buildix::filter::fields::{IsNull, Op, Ops};
+use buildix::filter::Filter as _;
+use buildix::query::{FromQuery, QueryError};
+use buildix_derive::{Filter, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::Postgres;
+
+#[test]
+fn test_from_query() {
+    let filter =
+        UserFilter::from_query("?age__gt=18&age__lte=65&id__in=1,2&id=3&name__icontains=Bob%20M")
+            .unwrap();
+
+    assert_eq!(filter.age, Ops(vec![Op::Gt(18), Op::Lte(65)]));
+    assert_eq!(filter.id, vec![1, 2, 3]);
+    assert_eq!(filter.name, Ops(vec![Op::IContains("Bob M".to_string())]));
+
+    let mut query = UserQuery {
+        filter,
+        ..Default::default()
+    };
+    let (q, v) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id FROM user WHERE (id IN ($1, $2, $3) AND (age > $4 AND age <= $5) AND LOWER(name) LIKE $6)"#
+    );
+    assert_eq!(v.len(), 6);
+    assert_eq!(v[5], buildix::Value::String("%bob m%".to_string()));
+}
+
+#[test]
+fn test_from_query_nested() {
+    let filter = UserFilter::from_query("deleted=false&either.email=a%40b.c&either.role=admin")
+        .unwrap();
+
+    assert_eq!(filter.deleted, Some(IsNull::from(false)));
+    assert_eq!(filter.either.email, Some("a@b.c".to_string()));
+    assert_eq!(filter.either.role, Some("admin".to_string()));
+
+    let mut query = UserQuery {
+        filter,
+        ..Default::default()
+    };
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id FROM user WHERE (deleted IS NOT NULL AND (email = $1 OR role = $2))"#
+    );
+}
+
+#[test]
+fn test_from_query_column() {
+    // operators are rendered with column of field (qualified by table)
+    let filter = ColumnFilter::from_query("min_age__gt=18&ids=1,2&removed=true").unwrap();
+    let result = filter
+        .process_filter::<buildix::dialect::Postgres>(&Default::default())
+        .unwrap();
+    assert_eq!(
+        result.clause,
+        r#"(u.age > ? AND u.id IN (?, ?) AND u.deleted_at IS NULL)"#
+    );
+    assert_eq!(result.values.len(), 3);
+}
+
+#[test]
+fn test_from_query_errors() {
+    let err = |query: &str| UserFilter::from_query(query).unwrap_err();
+
+    assert_eq!(
+        err("age__gt=18&id=x"),
+        QueryError {
+            param: "id".to_string(),
+            message: "invalid value `x`: invalid digit found in string".to_string(),
+        }
+    );
+
+    // operator not allowed for field
+    assert_eq!(err("age__ne=1").param, "age__ne");
+    assert_eq!(
+        err("age__ne=1").message,
+        "operator `ne` is not allowed".to_string()
+    );
+
+    // eq is always allowed
+    let filter = UserFilter::from_query("age__eq=42&id__eq=7").unwrap();
+    assert_eq!(filter.age, Ops(vec![Op::Eq(42)]));
+    assert_eq!(filter.id, vec![7]);
+
+    // unknown fields (also in nested filter)
+    assert_eq!(err("unknown=1").message, "unknown field `unknown`");
+    assert_eq!(err("either.unknown=1").param, "either.unknown");
+    assert_eq!(err("either=1").message, "missing field");
+
+    assert_eq!(
+        err("deleted__gt=1").message,
+        "operator `gt` is not allowed".to_string()
+    );
+    assert_eq!(err("deleted=maybe").message, "invalid boolean `maybe`");
+}
+
+#[test]
+fn test_from_query_matches() {
+    let filter = UserFilter::from_query("age__gte=18&age__lt=30&name__icontains=bob").unwrap();
+
+    assert!(filter.matches(&Row(20, "Bobby")));
+    assert!(!filter.matches(&Row(30, "Bobby")));
+    assert!(!filter.matches(&Row(20, "John")));
+}
+
+struct Row(i32, &'static str);
+
+impl buildix::filter::Fields for Row {
+    fn field(&self, name: &str) -> Option<buildix::Value> {
+        match name {
+            "age" => Some(self.0.into()),
+            "name" => Some(self.1.into()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Filter)]
+#[buildix(from_query)]
+struct UserFilter {
+    #[buildix(ops = "in")]
+    id: Vec<i64>,
+
+    #[buildix(ops = "gt, gte, lt, lte")]
+    age: Ops<i32>,
+
+    #[buildix(ops = "icontains")]
+    name: Ops<String>,
+
+    #[buildix(ops = "isnull")]
+    deleted: Option<IsNull>,
+
+    either: EitherFilter,
+}
+
+#[derive(Debug, Default, Filter)]
+#[buildix(from_query)]
+struct ColumnFilter {
+    #[buildix(table = "u", column = "age", ops = "gt")]
+    min_age: Ops<i64>,
+
+    #[buildix(table = "u", column = "id", ops = "in")]
+    ids: Vec<i64>,
+
+    #[buildix(table = "u", column = "deleted_at", ops = "isnull")]
+    removed: Option<IsNull>,
+}
+
+#[derive(Debug, Default, Filter)]
+#[buildix(from_query, operator = "OR")]
+struct EitherFilter {
+    email: Option<String>,
+    role: Option<String>,
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserQuery {
+    #[buildix(select)]
+    select: User,
+
+    #[buildix(filter)]
+    filter: UserFilter,
+}
+
+#[derive(Default, buildix_derive::Select)]
+#[buildix(table = "user")]
+struct User {
+    id: i64,
+}
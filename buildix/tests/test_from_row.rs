@@ -0,0 +1,123 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix::execute::Fetch;
+use buildix_derive::{Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::sqlite::{Sqlite, SqliteConnection};
+use sqlx::Connection;
+
+async fn connect() -> SqliteConnection {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::query(
+        "CREATE TABLE user (id INTEGER NOT NULL, name TEXT NOT NULL, mail TEXT, age INTEGER)",
+    )
+    .execute(&mut conn)
+    .await
+    .unwrap();
+    sqlx::query("INSERT INTO user (id, name, mail, age) VALUES (1, 'peter', 'peter@example.com', 42), (2, 'john', NULL, NULL)")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    conn
+}
+
+#[tokio::test]
+async fn test_from_row() {
+    let mut conn = connect().await;
+    let mut query = UserBuilder::default();
+    let (q, _) = query.to_sql::<Sqlite>().unwrap();
+
+    let users: Vec<SelectUser> = sqlx::query_as(&q).fetch_all(&mut conn).await.unwrap();
+
+    assert_eq!(
+        users,
+        vec![
+            SelectUser {
+                id: 1,
+                name: "peter".to_string(),
+                email: Some("peter@example.com".to_string()),
+                age: Some(42),
+                is_adult: true,
+            },
+            SelectUser {
+                id: 2,
+                name: "john".to_string(),
+                email: None,
+                age: None,
+                is_adult: false,
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_from_row_projection() {
+    let mut conn = connect().await;
+    let mut query = UserBuilder::default();
+    query.fields = vec!["id".to_string(), "age".to_string()];
+
+    // fields that are not selected are left with default values
+    let users: Vec<SelectUser> = query.fetch_all(&mut conn).await.unwrap();
+    assert_eq!(
+        users[0],
+        SelectUser {
+            id: 1,
+            name: "".to_string(),
+            email: None,
+            age: Some(42),
+            is_adult: false,
+        },
+    );
+}
+
+#[tokio::test]
+async fn test_from_row_missing_column() {
+    let mut conn = connect().await;
+    let mut query = UserBuilder::default();
+    query.fields = vec!["age".to_string()];
+
+    // id is always required
+    let result = query.fetch_all(&mut conn).await;
+    assert!(matches!(
+        result,
+        Err(buildix::Error::Sqlx(sqlx::Error::ColumnNotFound(column))) if column == "id"
+    ));
+
+    // missing column which is not removed by projection is an error, even for Option
+    let result: Result<Vec<SelectUser>, _> =
+        sqlx::query_as("SELECT u.id, u.name, COALESCE(age, 0) > 18 AS is_adult, age FROM user AS u")
+            .fetch_all(&mut conn)
+            .await;
+    assert!(matches!(result, Err(sqlx::Error::ColumnNotFound(column)) if column == "email"));
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(fields)]
+    fields: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Select)]
+#[buildix(from(table(name = "user", alias = "u")))]
+struct SelectUser {
+    #[buildix(table = "u")]
+    id: i64,
+
+    #[buildix(table = "u", default)]
+    name: String,
+
+    #[buildix(table = "u", column = "mail")]
+    email: Option<String>,
+
+    age: Option<i64>,
+
+    #[buildix(expr = "COALESCE(age, 0) > 18", default)]
+    is_adult: bool,
+}
@@ -0,0 +1,83 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix::execute::Execute;
+use buildix_derive::{DeleteBuilder, Filter};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Executor, Postgres};
+
+#[test]
+fn test_full_table_refused() {
+    let mut delete = DeleteUser::default();
+    let err = delete.to_sql::<Postgres>().unwrap_err();
+    assert!(matches!(err, buildix::Error::FullTable(ref table) if table == "user"));
+
+    // empty vec and nested filter without values are not filters either
+    delete.filter.name = None;
+    assert!(delete.to_sql::<Postgres>().is_err());
+
+    delete.id = vec![1];
+    let (q, _) = delete.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, "DELETE FROM user WHERE id IN ($1)");
+}
+
+#[test]
+fn test_full_table_runtime_flag() {
+    let mut delete = DeleteUser::default();
+    delete.all = true;
+    let (q, _) = delete.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, "DELETE FROM user");
+}
+
+#[test]
+fn test_full_table_allowed() {
+    let mut delete = DeleteSessions::default();
+    let (q, _) = delete.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, "DELETE FROM session");
+}
+
+#[tokio::test]
+async fn test_full_table_execute() {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    conn.execute("CREATE TABLE user (id INTEGER NOT NULL, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+    conn.execute("INSERT INTO user (id, name) VALUES (1, 'peter'), (2, 'john')")
+        .await
+        .unwrap();
+
+    let mut delete = DeleteUser::default();
+    assert!(delete.execute(&mut conn).await.is_err());
+
+    delete.all = true;
+    assert_eq!(delete.execute(&mut conn).await.unwrap(), 2);
+}
+
+#[derive(Default, DeleteBuilder)]
+#[buildix(table = "user")]
+struct DeleteUser {
+    #[buildix(filter)]
+    id: Vec<i64>,
+
+    #[buildix(filter)]
+    filter: UserFilter,
+
+    #[buildix(allow_full_table)]
+    all: bool,
+}
+
+#[derive(Default, Filter)]
+struct UserFilter {
+    name: Option<String>,
+}
+
+#[derive(Default, DeleteBuilder)]
+#[buildix(table = "session", allow_full_table)]
+struct DeleteSessions {
+    #[buildix(filter)]
+    user_id: Option<i64>,
+}
@@ -0,0 +1,148 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use buildix::Select;
+use buildix_derive::{DeleteBuilder, Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::sqlite::{Sqlite, SqliteConnection};
+use sqlx::{Connection, Postgres};
+
+#[test]
+fn test_borrowed_filter() {
+    let name = String::from("peter");
+    let mut query = UserBuilder {
+        select: vec![],
+        filter: UserFilter {
+            name: Some(&name),
+            ids: vec![1, 2],
+        },
+    };
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT id, name FROM user WHERE (LOWER(name) = LOWER($1) AND ids IN ($2, $3))"#
+    );
+}
+
+#[test]
+fn test_generic_builder() {
+    let mut query = PageBuilder::<SelectUser> {
+        select: vec![],
+        limit: Some(10),
+    };
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(q, r#"SELECT id, name FROM user LIMIT 10"#);
+
+    let mut query = PageBuilder::<SelectOrder> {
+        select: vec![],
+        limit: None,
+    };
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(q, r#"SELECT id FROM order"#);
+}
+
+#[test]
+fn test_generic_select() {
+    assert_eq!(
+        <WithTotal<SelectUser>>::get_fields_str::<Postgres>(),
+        "id AS item_id, name AS item_name, total"
+    );
+    assert_eq!(
+        <WithTotal<SelectOrder>>::get_fields_str::<Postgres>(),
+        "id AS item_id, total"
+    );
+}
+
+#[test]
+fn test_borrowed_delete() {
+    let name = String::from("peter");
+    let mut query = DeleteUser { name: &name };
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(q, r#"DELETE FROM user WHERE name = $1"#);
+}
+
+#[tokio::test]
+async fn test_generic_from_row() {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    let q = format!(
+        "SELECT {} FROM (SELECT 1 AS id, 'peter' AS name, 42 AS total)",
+        <WithTotal<SelectUser>>::get_fields_str::<Sqlite>()
+    );
+
+    let rows: Vec<WithTotal<SelectUser>> = sqlx::query_as(&q).fetch_all(&mut conn).await.unwrap();
+
+    assert_eq!(
+        rows,
+        vec![WithTotal {
+            item: SelectUser {
+                id: 1,
+                name: "peter".to_string(),
+            },
+            total: 42,
+        }]
+    );
+}
+
+#[derive(SelectBuilder)]
+struct UserBuilder<'a> {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    filter: UserFilter<'a>,
+}
+
+#[derive(Default, Filter)]
+struct UserFilter<'a> {
+    #[buildix(expr = "LOWER(name) = LOWER(?)")]
+    name: Option<&'a str>,
+
+    ids: Vec<i32>,
+}
+
+#[derive(SelectBuilder)]
+struct PageBuilder<T>
+where
+    T: Select,
+{
+    #[buildix(select)]
+    select: Vec<T>,
+
+    #[buildix(limit)]
+    limit: Option<i32>,
+}
+
+#[derive(Debug, PartialEq, Select)]
+#[buildix(table = "user")]
+struct SelectUser {
+    id: i64,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Select)]
+#[buildix(table = "order")]
+struct SelectOrder {
+    id: i64,
+}
+
+#[derive(Debug, PartialEq, Select)]
+#[buildix(table = "user")]
+struct WithTotal<T> {
+    #[buildix(flatten)]
+    item: T,
+
+    total: i64,
+}
+
+#[derive(DeleteBuilder)]
+#[buildix(table = "user")]
+struct DeleteUser<'a> {
+    #[buildix(filter)]
+    name: &'a str,
+}
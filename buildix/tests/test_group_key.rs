@@ -0,0 +1,174 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix_derive::{Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::Postgres;
+
+#[test]
+fn test_group_key() {
+    let mut query = ReportBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    // static group is used without grouping key
+    assert_eq!(
+        q,
+        r#"SELECT created AS period, SUM(total) AS total FROM order GROUP BY created"#
+    );
+
+    query.period = "week".to_string();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT DATE_TRUNC('week', created) AS period, SUM(total) AS total FROM order GROUP BY DATE_TRUNC('week', created)"#
+    );
+
+    query.fields = vec!["period".to_string()];
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT DATE_TRUNC('week', created) AS period FROM order GROUP BY DATE_TRUNC('week', created)"#
+    );
+}
+
+#[test]
+fn test_group_key_replace_entry() {
+    let mut query = StatusPeriodReportBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT status, created AS period, SUM(total) AS total FROM order GROUP BY status, created"#
+    );
+
+    // only entry of selected field is replaced
+    query.period = Some("month".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT status, DATE_TRUNC('month', created) AS period, SUM(total) AS total FROM order GROUP BY status, DATE_TRUNC('month', created)"#
+    );
+}
+
+#[test]
+fn test_group_flag() {
+    let mut query = GroupFlagBuilder::default();
+    query.group = true;
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT status, SUM(total) AS total FROM order GROUP BY status"#
+    );
+}
+
+#[test]
+fn test_group_key_invalid() {
+    let mut query = ReportBuilder::default();
+    query.period = "year".to_string();
+
+    assert!(matches!(
+        query.to_sql::<Postgres>(),
+        Err(buildix::Error::InvalidGroup(key)) if key == "year"
+    ));
+}
+
+#[test]
+fn test_group_key_extend() {
+    let mut query = StatusReportBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT status, SUM(total) AS total FROM order GROUP BY status"#
+    );
+
+    query.group = Some("user");
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT status, SUM(total) AS total FROM order GROUP BY status, user_id"#
+    );
+}
+
+#[derive(Default, SelectBuilder)]
+struct ReportBuilder {
+    #[buildix(select)]
+    select: Vec<Report>,
+
+    #[buildix(group(
+        key(name = "day", expr = "DATE_TRUNC('day', created)"),
+        key(name = "week", expr = "DATE_TRUNC('week', created)"),
+        key(name = "month", expr = "DATE_TRUNC('month', created)"),
+        select = "period"
+    ))]
+    period: String,
+
+    #[buildix(fields)]
+    fields: Vec<String>,
+}
+
+#[derive(Default, Select)]
+#[buildix(table = "order", group = "period")]
+struct Report {
+    #[buildix(column = "created")]
+    period: String,
+
+    #[buildix(aggregate = "sum")]
+    total: i64,
+}
+
+#[derive(Default, SelectBuilder)]
+struct StatusReportBuilder {
+    #[buildix(select)]
+    select: Vec<StatusReport>,
+
+    #[buildix(group(key(name = "user", expr = "user_id"), extend))]
+    group: Option<&'static str>,
+}
+
+#[derive(Default, Select)]
+#[buildix(table = "order", group = "status")]
+struct StatusReport {
+    status: i32,
+
+    #[buildix(aggregate = "sum")]
+    total: i64,
+}
+
+#[derive(Default, SelectBuilder)]
+struct StatusPeriodReportBuilder {
+    #[buildix(select)]
+    select: Vec<StatusPeriodReport>,
+
+    #[buildix(group(key(name = "month", expr = "DATE_TRUNC('month', created)"), select = "period"))]
+    period: Option<String>,
+}
+
+#[derive(Default, Select)]
+#[buildix(table = "order", group = "status", group = "period")]
+struct StatusPeriodReport {
+    status: i32,
+
+    #[buildix(column = "created")]
+    period: String,
+
+    #[buildix(aggregate = "sum")]
+    total: i64,
+}
+
+#[derive(Default, SelectBuilder)]
+struct GroupFlagBuilder {
+    #[buildix(select)]
+    select: Vec<StatusReport>,
+
+    #[buildix(group)]
+    group: bool,
+}
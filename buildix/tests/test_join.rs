@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
 
 use buildix::sort::Sort;
 use buildix_derive::{Filter, Select, SelectBuilder};
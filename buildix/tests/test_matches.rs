@@ -0,0 +1,158 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix::filter::fields::IsNull;
+use buildix::filter::Fields;
+use buildix::Value;
+use buildix_derive::{Filter, Select};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+
+fn users() -> Vec<SelectUser> {
+    vec![
+        SelectUser {
+            id: 1,
+            name: "peter".to_string(),
+            age: Some(42),
+            role: Role::Admin,
+        },
+        SelectUser {
+            id: 2,
+            name: "john".to_string(),
+            age: None,
+            role: Role::User,
+        },
+        SelectUser {
+            id: 3,
+            name: "paul".to_string(),
+            age: Some(17),
+            role: Role::User,
+        },
+    ]
+}
+
+fn matching(filter: &UserFilter) -> Vec<i64> {
+    users()
+        .iter()
+        .filter(|u| filter.matches(*u))
+        .map(|u| u.id)
+        .collect()
+}
+
+#[test]
+fn test_matches() {
+    // filter without values matches all rows
+    let mut filter = UserFilter::default();
+    assert_eq!(matching(&filter), vec![1, 2, 3]);
+
+    filter.name = Some("john".to_string());
+    assert_eq!(matching(&filter), vec![2]);
+
+    // vec membership
+    filter.name = None;
+    filter.id = vec![1, 3];
+    assert_eq!(matching(&filter), vec![1, 3]);
+
+    // custom expression is evaluated by function
+    filter.min_age = Some(18);
+    assert_eq!(matching(&filter), vec![1]);
+
+    // null checks
+    let mut filter = UserFilter::default();
+    filter.age = Some(IsNull::from(true));
+    assert_eq!(matching(&filter), vec![2]);
+    filter.age = Some(IsNull::from(false));
+    assert_eq!(matching(&filter), vec![1, 3]);
+}
+
+#[test]
+fn test_matches_nested() {
+    // nested filter with OR operator
+    let mut filter = UserFilter::default();
+    filter.either.name = Some("peter".to_string());
+    filter.either.id = Some(3);
+    assert_eq!(matching(&filter), vec![1, 3]);
+
+    filter.id = vec![3];
+    assert_eq!(matching(&filter), vec![3]);
+}
+
+#[test]
+fn test_matches_expr() {
+    // expression without function is not evaluated, so row is never matched when it is set
+    let alice = SelectUser {
+        id: 4,
+        name: "alice".to_string(),
+        age: None,
+        role: Role::User,
+    };
+    let mut filter = ExprFilter::default();
+    assert!(filter.matches(&alice));
+
+    filter.name = Some("BOB".to_string());
+    assert!(!filter.matches(&alice));
+}
+
+#[test]
+fn test_fields() {
+    let user = &users()[0];
+    assert_eq!(user.field("id"), Some(Value::I64(1)));
+    assert_eq!(user.field("age"), Some(Value::I32(42)));
+    assert_eq!(users()[1].field("age"), Some(Value::Null(buildix::value::Type::I32)));
+
+    // field without value (type is not convertible) and unknown field
+    assert_eq!(user.field("role"), None);
+    assert_eq!(user.field("unknown"), None);
+}
+
+#[derive(Debug, Default, Filter)]
+struct UserFilter {
+    id: Vec<i64>,
+
+    name: Option<String>,
+
+    age: Option<IsNull>,
+
+    #[buildix(expr = "age >= ?", matches = "min_age")]
+    min_age: Option<i32>,
+
+    either: EitherFilter,
+}
+
+#[derive(Debug, Default, Filter)]
+struct ExprFilter {
+    #[buildix(expr = "LOWER(name) = ?")]
+    name: Option<String>,
+}
+
+#[derive(Debug, Default, Filter)]
+#[buildix(operator = "OR")]
+struct EitherFilter {
+    name: Option<String>,
+    id: Option<i64>,
+}
+
+fn min_age<R: Fields + ?Sized>(min: &Option<i32>, row: &R) -> Option<bool> {
+    let min = (*min)?;
+    Some(matches!(row.field("age"), Some(Value::I32(age)) if age >= min))
+}
+
+#[derive(Debug, Default, PartialEq)]
+enum Role {
+    Admin,
+    #[default]
+    User,
+}
+
+#[derive(Debug, Select)]
+#[buildix(table = "user")]
+struct SelectUser {
+    id: i64,
+    name: String,
+    age: Option<i32>,
+
+    #[buildix(default)]
+    role: Role,
+}
@@ -0,0 +1,97 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix_derive::{Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::Postgres;
+
+#[test]
+fn test_projection() {
+    let mut query = ProjectionBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT u.id, u.name, u.mail AS email, COUNT(o.id) AS orders FROM user AS u"#
+    );
+
+    // order of fields is given by select
+    query.fields = vec!["email".to_string(), "name".to_string()];
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT u.name, u.mail AS email FROM user AS u"#);
+
+    query.distinct = true;
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT DISTINCT u.name, u.mail AS email FROM user AS u"#
+    );
+}
+
+#[test]
+fn test_projection_invalid_field() {
+    let mut query = ProjectionBuilder::default();
+    query.fields = vec!["name".to_string(), "password".to_string()];
+
+    match query.to_sql::<Postgres>() {
+        Err(buildix::Error::InvalidField(field)) => assert_eq!(field, "password"),
+        _ => panic!("expected invalid field error"),
+    }
+}
+
+#[test]
+fn test_projection_option() {
+    let mut query = OptionProjectionBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT u.id, u.name, u.mail AS email, COUNT(o.id) AS orders FROM user AS u"#
+    );
+
+    query.fields = Some(vec!["id".to_string()]);
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT u.id FROM user AS u"#);
+}
+
+#[derive(Default, SelectBuilder)]
+struct ProjectionBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(fields)]
+    fields: Vec<String>,
+
+    #[buildix(distinct)]
+    distinct: bool,
+}
+
+#[derive(Default, SelectBuilder)]
+struct OptionProjectionBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(fields)]
+    fields: Option<Vec<String>>,
+}
+
+// fields that are not selected are left with default value when fetched
+#[derive(Default, Select)]
+#[buildix(from(table(name = "user", alias = "u")))]
+struct SelectUser {
+    #[buildix(table = "u")]
+    id: i32,
+
+    #[buildix(table = "u")]
+    #[buildix(default)]
+    name: String,
+
+    #[buildix(table = "u", column = "mail")]
+    #[buildix(default)]
+    email: Option<String>,
+
+    #[buildix(expr = "COUNT(o.id)")]
+    #[buildix(default)]
+    orders: Option<i64>,
+}
@@ -15,7 +15,7 @@ fn test_readme_select() {
 
     assert_eq!(
         q,
-        r#"SELECT u.name, u.email, u.custom_age AS age, IF(age > 18, true, false) AS is_adult, COALESCE(other, "") AS other FROM user AS u, INNER JOIN order o (o.user_id = u.id) WHERE (priority = ? OR age ISNULL) GROUP BY name, email ORDER BY age ASC"#
+        r#"SELECT u.name, u.email, u.custom_age AS age, IF(age > 18, true, false) AS is_adult, COALESCE(other, "") AS other FROM user AS u, INNER JOIN order o (o.user_id = u.id) WHERE (priority = $1 OR age IS NULL) GROUP BY u.name, u.email ORDER BY age ASC"#
     );
 
     qb.filter.inner.value = Some(42);
@@ -24,7 +24,7 @@ fn test_readme_select() {
 
     assert_eq!(
         q,
-        r#"SELECT u.name, u.email, u.custom_age AS age, IF(age > 18, true, false) AS is_adult, COALESCE(other, "") AS other FROM user AS u, INNER JOIN order o (o.user_id = u.id) WHERE (priority = ? OR age ISNULL OR (value = ? AND value2 = ?)) GROUP BY name, email ORDER BY age ASC"#
+        r#"SELECT u.name, u.email, u.custom_age AS age, IF(age > 18, true, false) AS is_adult, COALESCE(other, "") AS other FROM user AS u, INNER JOIN order o (o.user_id = u.id) WHERE (priority = $1 OR age IS NULL OR (value = $2 AND value2 = $3)) GROUP BY u.name, u.email ORDER BY age ASC"#
     );
 }
 
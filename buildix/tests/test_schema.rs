@@ -0,0 +1,106 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use buildix_derive::{DeleteBuilder, Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::Postgres;
+
+#[test]
+fn test_schema() {
+    let mut query = UserOrdersBuilder::default();
+    query.filter.name = Some("peter".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"WITH recent AS (SELECT user_id, SUM(price) AS total FROM order GROUP BY user_id) SELECT u.id, u.name, u.email AS mail, COUNT(o.id) AS orders, r.total FROM user AS u, INNER JOIN order o (o.user_id = u.id), INNER JOIN recent r (r.user_id = u.id) WHERE name = $1 GROUP BY u.id, u.name, u.email, r.total"#
+    );
+}
+
+#[test]
+fn test_schema_delete() {
+    let mut query = DeleteOrder { user_id: 1 };
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(q, r#"DELETE FROM order WHERE user_id = $1"#);
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserOrdersBuilder {
+    #[buildix(select)]
+    select: Vec<UserOrders>,
+
+    #[buildix(filter)]
+    filter: UserFilter,
+}
+
+#[derive(Default, Filter)]
+#[buildix(schema = "tests/schema.sql")]
+struct UserFilter {
+    name: Option<String>,
+
+    #[buildix(table = "user")]
+    age: Option<i32>,
+
+    // nested filters are not columns
+    inner: InnerFilter,
+
+    #[buildix(expr = "LOWER(email) = ?")]
+    lower_email: Option<String>,
+}
+
+#[derive(Default, Filter)]
+#[buildix(schema = "tests/schema.sql", operator = "OR")]
+struct InnerFilter {
+    note: Option<String>,
+}
+
+// columns of common table expressions are not checked
+#[derive(Default, Select)]
+#[buildix(schema = "tests/schema.sql")]
+#[buildix(with(name = "recent", builder = "RecentBuilder"))]
+#[buildix(from(table(name = "user", alias = "u")))]
+#[buildix(from(join(name = "order", alias = "o", on = "o.user_id = u.id")))]
+#[buildix(from(join(name = "recent", alias = "r", on = "r.user_id = u.id")))]
+#[buildix(group = "id", group = "name", group = "mail", group = "total")]
+struct UserOrders {
+    #[buildix(table = "u")]
+    id: i32,
+
+    #[buildix(table = "u")]
+    name: String,
+
+    // nullable column
+    #[buildix(table = "u", column = "email")]
+    mail: Option<String>,
+
+    #[buildix(aggregate = "count", table = "o", column = "id")]
+    orders: i64,
+
+    #[buildix(table = "r")]
+    total: i64,
+}
+
+#[derive(Default, SelectBuilder)]
+struct RecentBuilder {
+    #[buildix(select)]
+    select: Vec<Recent>,
+}
+
+#[derive(Default, Select)]
+#[buildix(schema = "tests/schema.sql", table = "order", group = "user_id")]
+struct Recent {
+    user_id: i32,
+
+    #[buildix(aggregate = "sum", column = "price")]
+    total: i64,
+}
+
+#[derive(Default, DeleteBuilder)]
+#[buildix(schema = "tests/schema.sql", table = "order")]
+struct DeleteOrder {
+    #[buildix(filter)]
+    user_id: i32,
+}
@@ -0,0 +1,246 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix::Value;
+use buildix_derive::{DeleteBuilder, Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::Postgres;
+
+#[test]
+fn test_scope_select() {
+    // scope is not set
+    let mut query = UserBuilder::default();
+    let err = query.to_sql::<Postgres>().unwrap_err();
+    assert!(matches!(err, buildix::Error::MissingScope(ref column) if column == "tenant_id"));
+    assert_eq!(err.to_string(), "scope `tenant_id` is not set");
+
+    query.tenant = Some(7);
+    let (q, v) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT id, name FROM user WHERE tenant_id = $1"#);
+    assert_eq!(v, vec![Value::I64(7)]);
+
+    // OR of filter cannot escape scope
+    query.filter.name = Some("john".to_string());
+    query.filter.id = Some(1);
+    let (q, v) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id, name FROM user WHERE (tenant_id = $1) AND ((name = $2 OR id = $3))"#
+    );
+    assert_eq!(
+        v,
+        vec![Value::I64(7), Value::String("john".to_string()), Value::I64(1)]
+    );
+
+    // custom expression with OR is grouped as well
+    query.filter = UserFilter::default();
+    query.filter.search = Some("bob".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id, name FROM user WHERE (tenant_id = $1) AND (name = $2 OR email IS NULL)"#
+    );
+}
+
+#[test]
+fn test_scope_qualified() {
+    // column is qualified by alias of main table
+    let mut query = UserOrderBuilder::default();
+    query.tenant = Some(7);
+    let (q, v) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT u.id, o.id AS order_id FROM user AS u, INNER JOIN order o (o.user_id = u.id) WHERE u.tenant_id = $1"#
+    );
+    assert_eq!(v, vec![Value::I64(7)]);
+
+    // or by table name when there is no alias
+    let mut query = AccountRoleBuilder::default();
+    query.tenant = Some(7);
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT account.id, role.name FROM account, INNER JOIN role (role.account_id = account.id) WHERE account.tenant_id = $1"#
+    );
+}
+
+#[test]
+fn test_scope_delete() {
+    let mut delete = DeleteUser::default();
+    delete.id = vec![1, 2];
+    assert!(matches!(
+        delete.to_sql::<Postgres>(),
+        Err(buildix::Error::MissingScope(_))
+    ));
+
+    delete.tenant = Some(7);
+    let (q, v) = delete.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"DELETE FROM user WHERE (tenant_id = $1) AND (id IN ($2, $3))"#
+    );
+    assert_eq!(v, vec![Value::I64(7), Value::I64(1), Value::I64(2)]);
+
+    // scope is not a filter, all rows of tenant would be deleted
+    delete.id = vec![];
+    assert!(matches!(
+        delete.to_sql::<Postgres>(),
+        Err(buildix::Error::FullTable(_))
+    ));
+}
+
+#[test]
+fn test_scope_or_expr() {
+    // OR right after placeholder (without space) cannot escape scope
+    let mut query = BypassBuilder::default();
+    query.tenant = Some(7);
+    query.filter.paren = Some("bob".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id, name FROM user WHERE (tenant_id = $1) AND (name = $2 OR(1 = 1))"#
+    );
+
+    // neither can OR on new line
+    query.filter = BypassFilter::default();
+    query.filter.newline = Some("bob".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        "SELECT id, name FROM user WHERE (tenant_id = $1) AND (name = $2\nOR 1 = 1)"
+    );
+
+    let mut delete = DeleteBypass::default();
+    delete.tenant = Some(7);
+    delete.filter.paren = Some("bob".to_string());
+    let (q, _) = delete.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"DELETE FROM user WHERE (tenant_id = $1) AND (name = $2 OR(1 = 1))"#
+    );
+
+    delete.filter = BypassFilter::default();
+    delete.filter.newline = Some("bob".to_string());
+    let (q, _) = delete.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        "DELETE FROM user WHERE (tenant_id = $1) AND (name = $2\nOR 1 = 1)"
+    );
+}
+
+#[derive(Default, SelectBuilder)]
+#[buildix(scope = "tenant_id")]
+struct UserBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    filter: UserFilter,
+
+    #[buildix(scope)]
+    tenant: Option<i64>,
+}
+
+#[derive(Default, Filter)]
+#[buildix(operator = "OR")]
+struct UserFilter {
+    name: Option<String>,
+    id: Option<i64>,
+
+    #[buildix(expr = "name = ? OR email IS NULL")]
+    search: Option<String>,
+}
+
+#[derive(Default, DeleteBuilder)]
+#[buildix(table = "user", scope = "tenant_id")]
+struct DeleteUser {
+    #[buildix(filter)]
+    id: Vec<i64>,
+
+    #[buildix(scope)]
+    tenant: Option<i64>,
+}
+
+#[derive(Debug, Default, Select)]
+#[buildix(table = "user")]
+struct SelectUser {
+    id: i64,
+    name: String,
+}
+
+#[derive(Default, Filter)]
+struct BypassFilter {
+    #[buildix(expr = "name = ? OR(1 = 1)")]
+    paren: Option<String>,
+
+    #[buildix(expr = "name = ?\nOR 1 = 1")]
+    newline: Option<String>,
+}
+
+#[derive(Default, SelectBuilder)]
+#[buildix(scope = "tenant_id")]
+struct BypassBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    filter: BypassFilter,
+
+    #[buildix(scope)]
+    tenant: Option<i64>,
+}
+
+#[derive(Default, DeleteBuilder)]
+#[buildix(table = "user", scope = "tenant_id")]
+struct DeleteBypass {
+    #[buildix(filter)]
+    filter: BypassFilter,
+
+    #[buildix(scope)]
+    tenant: Option<i64>,
+}
+
+#[derive(Default, SelectBuilder)]
+#[buildix(scope = "tenant_id")]
+struct UserOrderBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUserOrder>,
+
+    #[buildix(scope)]
+    tenant: Option<i64>,
+}
+
+#[derive(Debug, Default, Select)]
+#[buildix(from(table(name = "user", alias = "u")))]
+#[buildix(from(join(name = "order", alias = "o", on = "o.user_id = u.id")))]
+struct SelectUserOrder {
+    #[buildix(table = "u")]
+    id: i64,
+
+    #[buildix(table = "o", column = "id")]
+    order_id: i64,
+}
+
+#[derive(Default, SelectBuilder)]
+#[buildix(scope = "tenant_id")]
+struct AccountRoleBuilder {
+    #[buildix(select)]
+    select: Vec<SelectAccountRole>,
+
+    #[buildix(scope)]
+    tenant: Option<i64>,
+}
+
+#[derive(Debug, Default, Select)]
+#[buildix(table = "account")]
+#[buildix(from(join(name = "role", on = "role.account_id = account.id")))]
+struct SelectAccountRole {
+    #[buildix(table = "account")]
+    id: i64,
+
+    #[buildix(table = "role")]
+    name: String,
+}
@@ -0,0 +1,242 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix::dialect::{Mssql, MySql};
+use buildix::execute::{Execute, Fetch};
+use buildix_derive::{DeleteBuilder, Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::sqlite::SqliteConnection;
+use sqlx::{Connection, Executor, Postgres, Sqlite};
+
+#[test]
+fn test_soft_delete_query() {
+    let mut delete = DeleteUser::default();
+    delete.id = vec![1, 2];
+
+    let (q, v) = delete.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"UPDATE user SET deleted_at = now() WHERE (deleted_at IS NULL) AND (id IN ($1, $2))"#
+    );
+    assert_eq!(v.len(), 2);
+
+    let (q, _) = delete.to_sql::<MySql>().unwrap();
+    assert_eq!(
+        q,
+        r#"UPDATE user SET deleted_at = NOW() WHERE (deleted_at IS NULL) AND (id IN (?, ?))"#
+    );
+
+    delete.limit = Some(10);
+    let (q, _) = delete.to_sql::<Sqlite>().unwrap();
+    assert_eq!(
+        q,
+        r#"UPDATE user SET deleted_at = CURRENT_TIMESTAMP WHERE rowid IN (SELECT rowid FROM user WHERE (deleted_at IS NULL) AND (id IN (?, ?)) LIMIT 10)"#
+    );
+
+    let (q, _) = delete.to_sql::<Mssql>().unwrap();
+    assert_eq!(
+        q,
+        r#"UPDATE TOP (10) user SET deleted_at = CURRENT_TIMESTAMP WHERE (deleted_at IS NULL) AND (id IN (@p1, @p2))"#
+    );
+}
+
+#[test]
+fn test_soft_delete_select() {
+    let mut query = UserBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT id, name FROM user WHERE deleted_at IS NULL"#);
+
+    query.filter.name = Some("john".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id, name FROM user WHERE (deleted_at IS NULL) AND (name = $1)"#
+    );
+
+    // deleted rows explicitly requested
+    query.with_deleted = true;
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT id, name FROM user WHERE name = $1"#);
+}
+
+#[test]
+fn test_soft_delete_or_expr() {
+    // OR of custom expression cannot escape soft delete
+    let mut query = UserBuilder::default();
+    query.filter.search = Some("bob".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id, name FROM user WHERE (deleted_at IS NULL) AND (name = $1 OR(1 = 1))"#
+    );
+}
+
+#[test]
+fn test_soft_delete_qualified() {
+    // column is qualified by alias of main table
+    let mut query = UserOrderBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT u.id, o.id AS order_id FROM user AS u, INNER JOIN order o (o.user_id = u.id) WHERE u.deleted_at IS NULL"#
+    );
+
+    // or by table name when there is no alias
+    let mut query = AccountRoleBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT account.id, role.name FROM account, INNER JOIN role (role.account_id = account.id) WHERE account.deleted_at IS NULL"#
+    );
+}
+
+#[test]
+fn test_soft_delete_flag() {
+    // flag column is rendered by boolean literals of dialect
+    let mut delete = DeleteFlaggedUser::default();
+    delete.id = vec![1];
+
+    let (q, _) = delete.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"UPDATE user SET deleted = TRUE WHERE (deleted = FALSE) AND (id IN ($1))"#
+    );
+
+    let (q, _) = delete.to_sql::<Mssql>().unwrap();
+    assert_eq!(
+        q,
+        r#"UPDATE user SET deleted = 1 WHERE (deleted = 0) AND (id IN (@p1))"#
+    );
+
+    let mut query = FlaggedUserBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, r#"SELECT id, name FROM user WHERE deleted = FALSE"#);
+
+    let (q, _) = query.to_sql::<Mssql>().unwrap();
+    assert_eq!(q, r#"SELECT id, name FROM user WHERE deleted = 0"#);
+}
+
+#[tokio::test]
+async fn test_soft_delete_execute() {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    conn.execute("CREATE TABLE user (id INTEGER NOT NULL, name TEXT NOT NULL, deleted_at TEXT)")
+        .await
+        .unwrap();
+    conn.execute("INSERT INTO user (id, name) VALUES (1, 'peter'), (2, 'john'), (3, 'paul')")
+        .await
+        .unwrap();
+
+    let mut delete = DeleteUser::default();
+    delete.id = vec![1, 2];
+    assert_eq!(delete.execute(&mut conn).await.unwrap(), 2);
+
+    // already deleted rows are not updated again
+    delete.id = vec![1, 2, 3];
+    assert_eq!(delete.execute(&mut conn).await.unwrap(), 1);
+    assert_eq!(delete.count, 1);
+
+    let users = UserBuilder::default().fetch_all(&mut conn).await.unwrap();
+    assert!(users.is_empty());
+
+    let mut query = UserBuilder::default();
+    query.with_deleted = true;
+    let users = query.fetch_all(&mut conn).await.unwrap();
+    assert_eq!(users.len(), 3);
+}
+
+#[derive(Default, DeleteBuilder)]
+#[buildix(table = "user", soft_delete = "deleted_at")]
+struct DeleteUser {
+    #[buildix(filter)]
+    id: Vec<i64>,
+
+    #[buildix(limit)]
+    limit: Option<i64>,
+
+    #[buildix(count)]
+    count: i64,
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    filter: UserFilter,
+
+    #[buildix(with_deleted)]
+    with_deleted: bool,
+}
+
+#[derive(Default, Filter)]
+struct UserFilter {
+    name: Option<String>,
+
+    #[buildix(expr = "name = ? OR(1 = 1)")]
+    search: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Select)]
+#[buildix(table = "user", soft_delete = "deleted_at")]
+struct SelectUser {
+    id: i64,
+    name: String,
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserOrderBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUserOrder>,
+}
+
+#[derive(Debug, Default, Select)]
+#[buildix(from(table(name = "user", alias = "u")), soft_delete = "deleted_at")]
+#[buildix(from(join(name = "order", alias = "o", on = "o.user_id = u.id")))]
+struct SelectUserOrder {
+    #[buildix(table = "u")]
+    id: i64,
+
+    #[buildix(table = "o", column = "id")]
+    order_id: i64,
+}
+
+#[derive(Default, SelectBuilder)]
+struct AccountRoleBuilder {
+    #[buildix(select)]
+    select: Vec<SelectAccountRole>,
+}
+
+#[derive(Debug, Default, Select)]
+#[buildix(table = "account", soft_delete = "deleted_at")]
+#[buildix(from(join(name = "role", on = "role.account_id = account.id")))]
+struct SelectAccountRole {
+    #[buildix(table = "account")]
+    id: i64,
+
+    #[buildix(table = "role")]
+    name: String,
+}
+
+#[derive(Default, DeleteBuilder)]
+#[buildix(table = "user", soft_delete = "deleted", soft_delete_flag)]
+struct DeleteFlaggedUser {
+    #[buildix(filter)]
+    id: Vec<i64>,
+}
+
+#[derive(Default, SelectBuilder)]
+struct FlaggedUserBuilder {
+    #[buildix(select)]
+    select: Vec<SelectFlaggedUser>,
+}
+
+#[derive(Debug, Default, Select)]
+#[buildix(table = "user", soft_delete = "deleted", soft_delete_flag)]
+struct SelectFlaggedUser {
+    id: i64,
+    name: String,
+}
@@ -0,0 +1,60 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use buildix::filter::Filter;
+use buildix_derive::{Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::Postgres;
+
+#[test]
+fn test_filter_values() {
+    assert_eq!(<i32 as Filter>::VALUES, Some(1));
+    assert_eq!(<Option<String> as Filter>::VALUES, Some(1));
+    assert_eq!(<Vec<i32> as Filter>::VALUES, None);
+    assert_eq!(<UserFilter as Filter>::VALUES, None);
+}
+
+#[test]
+fn test_checked_fragments() {
+    let mut query = UserBuilder::default();
+    query.filter.name = Some("peter".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"SELECT u.id, CASE WHEN u.age > 18 THEN 'adult' ELSE 'child' END AS category FROM user AS u, INNER JOIN profile p (p.user_id = u.id AND p.active) WHERE LOWER(u.name) = LOWER($1) GROUP BY u.id, CASE WHEN u.age > 18 THEN 'adult' ELSE 'child' END"#
+    );
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    filter: UserFilter,
+}
+
+// placeholders in string literals are not counted
+#[derive(Default, Filter)]
+struct UserFilter {
+    #[buildix(expr = "LOWER(u.name) = LOWER(?)")]
+    name: Option<String>,
+
+    #[buildix(expr = "u.note = '?' AND u.kind = ?")]
+    kind: Option<i32>,
+}
+
+#[derive(Default, Select)]
+#[buildix(from(table(name = "user", alias = "u")))]
+#[buildix(from(join(name = "profile", alias = "p", on = "p.user_id = u.id AND p.active")))]
+#[buildix(group = "id", group = "category")]
+struct SelectUser {
+    #[buildix(table = "u")]
+    id: i32,
+
+    #[buildix(expr = "CASE WHEN u.age > 18 THEN 'adult' ELSE 'child' END")]
+    category: String,
+}
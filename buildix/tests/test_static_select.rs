@@ -0,0 +1,88 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use buildix::Select;
+use buildix_derive::{Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::Postgres;
+
+#[test]
+fn test_static_select() {
+    assert_eq!(SelectUser::get_field_names(), &["id", "name"]);
+    assert_eq!(
+        SelectUser::get_query::<Postgres>(),
+        "SELECT id, u.name FROM user AS u"
+    );
+    assert_eq!(
+        <Vec<SelectUser> as Select>::get_fields::<Postgres>(),
+        &["id", "u.name"]
+    );
+    assert_eq!(
+        <Option<SelectUser> as Select>::get_table::<Postgres>(),
+        "user AS u"
+    );
+}
+
+#[test]
+fn test_select_single() {
+    let mut query = SingleUserBuilder {
+        select: SelectUser {
+            id: 0,
+            name: "".to_string(),
+        },
+        id: 42,
+    };
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, "SELECT id, u.name FROM user AS u WHERE id = $1");
+}
+
+#[test]
+fn test_select_option() {
+    let mut query = OptionUserBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, "SELECT id, u.name FROM user AS u WHERE id = $1");
+}
+
+#[test]
+fn test_select_vec() {
+    let mut query = UsersBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(q, "SELECT id, u.name FROM user AS u");
+}
+
+// builder with single row does not need Default for row
+#[derive(SelectBuilder)]
+struct SingleUserBuilder {
+    #[buildix(select)]
+    select: SelectUser,
+
+    #[buildix(filter)]
+    id: i32,
+}
+
+#[derive(Default, SelectBuilder)]
+struct OptionUserBuilder {
+    #[buildix(select)]
+    select: Option<SelectUser>,
+
+    #[buildix(filter)]
+    id: i32,
+}
+
+#[derive(Default, SelectBuilder)]
+struct UsersBuilder {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+}
+
+// row does not implement Default
+#[derive(Select)]
+#[buildix(from(table(name = "user", alias = "u")))]
+struct SelectUser {
+    id: i32,
+
+    #[buildix(table = "u")]
+    name: String,
+}
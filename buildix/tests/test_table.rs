@@ -0,0 +1,87 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use buildix::Select;
+use buildix_derive::Select;
+
+use sqlx::Postgres;
+
+#[test]
+fn test_table() {
+    assert_eq!(
+        OrderItem::get_query::<Postgres>(),
+        "SELECT id FROM order_item"
+    );
+    assert_eq!(Explicit::get_query::<Postgres>(), "SELECT id FROM users");
+    assert_eq!(
+        Lowercase::get_query::<Postgres>(),
+        "SELECT id FROM lowercase"
+    );
+    assert_eq!(
+        ScreamingOrderItem::get_query::<Postgres>(),
+        "SELECT id FROM SCREAMING_ORDER_ITEM"
+    );
+    // conventional `Select` prefix is stripped
+    assert_eq!(SelectUser::get_query::<Postgres>(), "SELECT id FROM user");
+    assert_eq!(Selection::get_query::<Postgres>(), "SELECT id FROM selection");
+    assert_eq!(
+        AliasOnly::get_query::<Postgres>(),
+        "SELECT a.id FROM alias_only AS a"
+    );
+    assert_eq!(
+        JoinOnly::get_query::<Postgres>(),
+        "SELECT join_only.id FROM join_only, LEFT JOIN user u (u.id = join_only.user_id)"
+    );
+}
+
+#[derive(Select)]
+struct OrderItem {
+    id: i32,
+}
+
+#[derive(Select)]
+struct SelectUser {
+    id: i32,
+}
+
+#[derive(Select)]
+struct Selection {
+    id: i32,
+}
+
+#[derive(Select)]
+#[buildix(table = "users")]
+struct Explicit {
+    id: i32,
+}
+
+#[derive(Select)]
+#[buildix(rename_all = "lowercase")]
+struct Lowercase {
+    id: i32,
+}
+
+#[derive(Select)]
+#[buildix(rename_all = "SCREAMING_SNAKE_CASE")]
+struct ScreamingOrderItem {
+    id: i32,
+}
+
+#[derive(Select)]
+#[buildix(from(table(alias = "a")))]
+struct AliasOnly {
+    #[buildix(table = "a")]
+    id: i32,
+}
+
+#[derive(Select)]
+#[buildix(from(join(
+    name = "user",
+    alias = "u",
+    on = "u.id = join_only.user_id",
+    join_type = "left"
+)))]
+struct JoinOnly {
+    #[buildix(table = "join_only")]
+    id: i32,
+}
@@ -0,0 +1,120 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::field_reassign_with_default)]
+
+use buildix::dialect::{Mssql, MySql, Postgres};
+use buildix::execute::Backend;
+use buildix::value::Type;
+use buildix::{ToValue, Value};
+use buildix_derive::{DeleteBuilder, Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+
+#[test]
+fn test_values() {
+    let name = "peter".to_string();
+    let mut query = UserBuilder::default();
+    query.filter.name = Some(&name);
+    query.filter.active = Some(true);
+    query.filter.id = vec![1, 2];
+
+    let (q, values) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id, name FROM user WHERE (name = $1 AND active = TRUE AND id IN ($2, $3))"#
+    );
+    assert_eq!(
+        values,
+        vec![
+            Value::String("peter".to_string()),
+            Value::I64(1),
+            Value::I64(2),
+        ]
+    );
+
+    // booleans are rendered by dialect
+    let (q, _) = query.to_sql::<Mssql>().unwrap();
+    assert_eq!(
+        q,
+        r#"SELECT id, name FROM user WHERE (name = @p1 AND active = 1 AND id IN (@p2, @p3))"#
+    );
+}
+
+#[test]
+fn test_typed_null() {
+    // NULL is bound by type of column, not as text
+    assert_eq!(None::<i64>.to_value(), Value::Null(Type::I64));
+    assert_eq!(None::<&str>.to_value(), Value::Null(Type::String));
+    assert_eq!(None::<uuid::Uuid>.to_value(), Value::Null(Type::Uuid));
+    assert_eq!(Some(1.5).to_value(), Value::F64(1.5));
+}
+
+#[tokio::test]
+async fn test_bind_values() {
+    use sqlx::Row;
+
+    let mut conn = <sqlx::SqliteConnection as sqlx::Connection>::connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let timestamp = chrono::DateTime::parse_from_rfc3339("2021-01-02T03:04:05Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    let arguments = <sqlx::Sqlite as Backend>::arguments(vec![
+        Value::F64(1.5),
+        Value::Bytes(vec![1, 2]),
+        Value::Timestamp(timestamp),
+        Value::Null(Type::I64),
+    ])
+    .unwrap();
+
+    let row = sqlx::query_with("SELECT ?, ?, ?, ?", arguments)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(row.get::<f64, _>(0), 1.5);
+    assert_eq!(row.get::<Vec<u8>, _>(1), vec![1, 2]);
+    assert_eq!(row.get::<chrono::DateTime<chrono::Utc>, _>(2), timestamp);
+    assert_eq!(row.get::<Option<i64>, _>(3), None);
+}
+
+#[test]
+fn test_delete_values() {
+    let mut query = DeleteUser { id: 42 };
+    let (q, values) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(q, r#"DELETE FROM user WHERE id = $1"#);
+    assert_eq!(values, vec![Value::I32(42)]);
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserBuilder<'a> {
+    #[buildix(select)]
+    select: Vec<SelectUser>,
+
+    #[buildix(filter)]
+    filter: UserFilter<'a>,
+}
+
+#[derive(Default, Filter)]
+struct UserFilter<'a> {
+    name: Option<&'a String>,
+
+    active: Option<bool>,
+
+    id: Vec<i64>,
+}
+
+#[derive(Default, Select)]
+#[buildix(table = "user")]
+struct SelectUser {
+    id: i64,
+    name: String,
+}
+
+#[derive(DeleteBuilder)]
+#[buildix(table = "user")]
+struct DeleteUser {
+    #[buildix(filter)]
+    id: i32,
+}
@@ -0,0 +1,186 @@
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+use buildix::Value;
+use buildix_derive::{Filter, Select, SelectBuilder};
+
+#[allow(unused_imports)]
+use buildix::prelude::*;
+use sqlx::Postgres;
+
+#[test]
+fn test_with() {
+    let mut query = UserReportBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"WITH recent AS (SELECT user_id, total FROM order WHERE status = $1) SELECT u.name, r.total FROM recent AS r, INNER JOIN user u (u.id = r.user_id)"#
+    );
+
+    query.filter.name = Some("peter".to_string());
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"WITH recent AS (SELECT user_id, total FROM order WHERE status = $1) SELECT u.name, r.total FROM recent AS r, INNER JOIN user u (u.id = r.user_id) WHERE u.name = $2"#
+    );
+}
+
+#[test]
+fn test_with_recursive() {
+    let mut query = TreeBuilder::default();
+    let (q, _) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"WITH RECURSIVE recent AS (SELECT user_id, total FROM order WHERE status = $1), tree(id, parent_id) AS (SELECT id, parent_id FROM category) SELECT id FROM tree"#
+    );
+}
+
+#[test]
+fn test_with_builder() {
+    // builder of common table expression is provided by field, so it has runtime values
+    let mut query = RecentReportBuilder::default();
+    query.recent.status = 3;
+    query.filter.name = Some("peter".to_string());
+    let (q, v) = query.to_sql::<Postgres>().unwrap();
+
+    assert_eq!(
+        q,
+        r#"WITH recent AS (SELECT user_id, total FROM order WHERE status = $1) SELECT u.name, r.total FROM recent AS r, INNER JOIN user u (u.id = r.user_id) WHERE u.name = $2"#
+    );
+    assert_eq!(v.len(), 2);
+}
+
+#[test]
+fn test_with_scope() {
+    // default builder of scoped common table expression has no scope
+    let mut query = ScopedReportBuilder::default();
+    assert!(matches!(
+        query.to_sql::<Postgres>(),
+        Err(buildix::Error::MissingScope(_))
+    ));
+
+    query.orders.tenant = Some(7);
+    let (q, v) = query.to_sql::<Postgres>().unwrap();
+    assert_eq!(
+        q,
+        r#"WITH scoped AS (SELECT user_id, total FROM order WHERE tenant_id = $1) SELECT user_id, total FROM scoped"#
+    );
+    assert_eq!(v, vec![Value::I64(7)]);
+}
+
+#[derive(Default, SelectBuilder)]
+struct RecentReportBuilder {
+    #[buildix(select)]
+    select: Vec<UserReport>,
+
+    #[buildix(filter)]
+    filter: UserReportFilter,
+
+    #[buildix(with = "recent")]
+    recent: RecentOrdersBuilder,
+}
+
+#[derive(Default, SelectBuilder)]
+struct ScopedReportBuilder {
+    #[buildix(select)]
+    select: Vec<ScopedReport>,
+
+    #[buildix(with = "scoped")]
+    orders: ScopedOrdersBuilder,
+}
+
+#[derive(Default, Select)]
+#[buildix(with(name = "scoped", builder = "ScopedOrdersBuilder"))]
+#[buildix(from(table(name = "scoped")))]
+struct ScopedReport {
+    user_id: i32,
+    total: i64,
+}
+
+#[derive(Default, SelectBuilder)]
+#[buildix(scope = "tenant_id")]
+struct ScopedOrdersBuilder {
+    #[buildix(select)]
+    select: Vec<RecentOrder>,
+
+    #[buildix(scope)]
+    tenant: Option<i64>,
+}
+
+#[derive(Default, SelectBuilder)]
+struct UserReportBuilder {
+    #[buildix(select)]
+    select: Vec<UserReport>,
+
+    #[buildix(filter)]
+    filter: UserReportFilter,
+}
+
+#[derive(Default, Filter)]
+struct UserReportFilter {
+    #[buildix(expr = "u.name = ?")]
+    name: Option<String>,
+}
+
+#[derive(Default, Select)]
+#[buildix(with(name = "recent", builder = "RecentOrdersBuilder"))]
+#[buildix(from(table(name = "recent", alias = "r")))]
+#[buildix(from(join(name = "user", alias = "u", on = "u.id = r.user_id")))]
+struct UserReport {
+    #[buildix(table = "u")]
+    name: String,
+
+    #[buildix(table = "r")]
+    total: i64,
+}
+
+#[derive(Default, SelectBuilder)]
+struct RecentOrdersBuilder {
+    #[buildix(select)]
+    select: Vec<RecentOrder>,
+
+    #[buildix(filter)]
+    status: i32,
+}
+
+#[derive(Default, Select)]
+#[buildix(from(table(name = "order")))]
+struct RecentOrder {
+    user_id: i32,
+    total: i64,
+}
+
+#[derive(Default, SelectBuilder)]
+struct TreeBuilder {
+    #[buildix(select)]
+    select: Vec<Tree>,
+}
+
+#[derive(Default, Select)]
+#[buildix(with(name = "recent", builder = "RecentOrdersBuilder"))]
+#[buildix(with(
+    name = "tree",
+    builder = "CategoryBuilder",
+    columns = "id, parent_id",
+    recursive
+))]
+#[buildix(from(table(name = "tree")))]
+struct Tree {
+    id: i32,
+}
+
+#[derive(Default, SelectBuilder)]
+struct CategoryBuilder {
+    #[buildix(select)]
+    select: Vec<Category>,
+}
+
+#[derive(Default, Select)]
+#[buildix(from(table(name = "category")))]
+struct Category {
+    id: i32,
+    parent_id: Option<i32>,
+}
@@ -0,0 +1,9 @@
+use buildix_derive::Select;
+
+#[derive(Default, Select)]
+#[buildix(table = "user", group = "name,")]
+struct UserNames {
+    name: String,
+}
+
+fn main() {}
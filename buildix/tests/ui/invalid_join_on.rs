@@ -0,0 +1,11 @@
+use buildix_derive::Select;
+
+#[derive(Default, Select)]
+#[buildix(from(table(name = "user", alias = "u")))]
+#[buildix(from(join(name = "profile", alias = "p", on = "p.user_id = = u.id")))]
+struct UserProfile {
+    #[buildix(table = "u")]
+    id: i32,
+}
+
+fn main() {}
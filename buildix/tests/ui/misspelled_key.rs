@@ -0,0 +1,9 @@
+use buildix_derive::Filter;
+
+#[derive(Default, Filter)]
+struct UserFilter {
+    #[buildix(exr = "name = ?")]
+    name: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use buildix_derive::Filter;
+
+#[derive(Default, Filter)]
+struct UserFilter {
+    #[buildix(expr = "name = ? OR email = ?")]
+    name: Option<String>,
+}
+
+fn main() {}
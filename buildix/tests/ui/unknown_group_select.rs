@@ -0,0 +1,22 @@
+use buildix_derive::{Select, SelectBuilder};
+
+#[derive(Default, SelectBuilder)]
+struct ReportBuilder {
+    #[buildix(select)]
+    select: Vec<Report>,
+
+    #[buildix(group(key(name = "week", expr = "DATE_TRUNC('week', created)"), select = "perod"))]
+    period: String,
+}
+
+#[derive(Default, Select)]
+#[buildix(table = "order", group = "period")]
+struct Report {
+    #[buildix(column = "created")]
+    period: String,
+
+    #[buildix(aggregate = "sum")]
+    total: i64,
+}
+
+fn main() {}
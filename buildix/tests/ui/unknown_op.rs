@@ -0,0 +1,11 @@
+use buildix::filter::fields::Ops;
+use buildix_derive::Filter;
+
+#[derive(Default, Filter)]
+#[buildix(from_query)]
+struct UserFilter {
+    #[buildix(ops = "gt, between")]
+    age: Ops<i32>,
+}
+
+fn main() {}
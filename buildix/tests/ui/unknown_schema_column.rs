@@ -0,0 +1,11 @@
+use buildix_derive::Select;
+
+// schema path is relative to project of trybuild (target/tests/trybuild/buildix)
+#[derive(Default, Select)]
+#[buildix(table = "user", schema = "${BUILDIX_TESTS_DIR}/schema.sql")]
+struct User {
+    id: i32,
+    nickname: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use buildix_derive::Select;
+
+// schema path is relative to project of trybuild (target/tests/trybuild/buildix)
+#[derive(Default, Select)]
+#[buildix(table = "account", schema = "${BUILDIX_TESTS_DIR}/schema.sql")]
+struct Account {
+    id: i32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use buildix_derive::Filter;
+
+#[derive(Default, Filter)]
+#[buildix(from_query)]
+struct UserFilter {
+    #[buildix(ops = "gt")]
+    age: Option<i64>,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use buildix_derive::{Select, SelectBuilder};
+
+#[derive(Default, SelectBuilder)]
+struct UserBuilder {
+    #[buildix(select)]
+    select: Vec<User>,
+
+    #[buildix(with_deleted)]
+    with_deleted: Option<bool>,
+}
+
+#[derive(Default, Select)]
+#[buildix(table = "user", soft_delete = "deleted_at")]
+struct User {
+    id: i32,
+}
+
+fn main() {}
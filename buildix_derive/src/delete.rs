@@ -19,6 +19,8 @@ pub struct Builder {
     // indent
     ident: syn::Ident,
 
+    generics: syn::Generics,
+
     // data
     data: ast::Data<util::Ignored, BuilderField>,
 
@@ -28,6 +30,31 @@ pub struct Builder {
     // map function
     #[darling(default)]
     map: Option<syn::Path>,
+
+    // DDL file to validate table and columns against
+    #[darling(default)]
+    schema: Option<darling::util::SpannedValue<String>>,
+
+    // column set to current timestamp instead of deleting rows
+    #[darling(default)]
+    soft_delete: String,
+
+    // soft delete column is boolean flag (set to true) instead of timestamp
+    #[darling(default)]
+    soft_delete_flag: bool,
+
+    // column which scopes every query (value is provided by `#[buildix(scope)]` field)
+    #[darling(default)]
+    scope: Option<String>,
+
+    // columns of deleted rows returned by `RETURNING` clause (all of them by default)
+    #[darling(default)]
+    returning: Option<String>,
+
+    // query without filter is allowed (otherwise only when `#[buildix(allow_full_table)]` field
+    // is true)
+    #[darling(default)]
+    allow_full_table: bool,
 }
 
 // Builder methods
@@ -43,7 +70,7 @@ impl Builder {
             .unwrap()
             .fields
             .iter()
-            .map(|x| *x)
+            .copied()
             .filter(|x| fun(x))
             .collect()
     }
@@ -58,7 +85,7 @@ impl Builder {
     where
         T: Fn(&BuilderField) -> bool,
     {
-        self.filter_fields(call).iter().map(|x| *x).next()
+        self.filter_fields(call).first().copied()
     }
 
     // validate_single validates if field is single
@@ -66,49 +93,76 @@ impl Builder {
     where
         T: Fn(&BuilderField) -> bool,
     {
-        if let Some(invalid) = self.filter_fields(call).iter().skip(1).next() {
+        if let Some(invalid) = self.filter_fields(call).get(1) {
             abort!(invalid.ident, err);
         }
     }
 
-    // write_simple_query writes `get_simple_query` method
-    pub fn write_get_simple_query(&self, tokens: &mut TokenStream) {
-        let ident = &self.ident;
-        let simple_query = format!("DELETE FROM {}", self.table);
-        tokens.extend(quote! {
-            impl #ident {
-                fn get_simple_query(&self) -> &'static str {
-                    #simple_query
-                }
-            }
-        });
-    }
-
     // write filter
-    pub fn write_filter(&self, _tokens: &mut TokenStream, _target_string: &syn::Ident) {
+    pub fn write_filter(&self, _tokens: &mut TokenStream) {
         // now write filter implementation
 
         // prepare fields
         let fields: Vec<crate::filter::process::Field> = self
             .list_filter_fields()
             .iter()
-            .map(|x| x.clone())
+            .copied()
             .map(|f| f.into())
             .collect();
 
         // process filter
-        crate::filter::process::process(&self.ident, fields, " AND ".to_string(), _tokens)
+        crate::filter::process::process(
+            &self.ident,
+            &self.generics,
+            fields,
+            " AND ".to_string(),
+            _tokens,
+        )
     }
 
-    // write limit
-    pub fn write_limit(&self, tokens: &mut TokenStream, target_string: &syn::Ident) {
+    // write limit (value of limit field, query is limited by dialect)
+    pub fn write_limit(&self, tokens: &mut TokenStream) {
         if let Some(field) = self.first_field(|x| x.limit) {
             let ident = field.ident.as_ref().unwrap();
+            tokens.extend(quote! {
+                fn get_limit(&self) -> Option<u64> {
+                    __Limit::get_limit(self.#ident)
+                }
+            });
+        }
+    }
+
+    // write scope (clause of scope is always applied, query fails when scope is not set)
+    pub fn write_scope(&self, tokens: &mut TokenStream) {
+        if let (Some(column), Some(field)) = (&self.scope, self.first_field(|x| x.scope)) {
+            let ident = field.ident.as_ref().unwrap();
+            tokens.extend(quote! {
+                let (scope_clause, scope_value) = ::buildix::scope::scope_clause(#column, &self.#ident)?;
+                clauses.push(scope_clause);
+                values.push(scope_value);
+            });
+        }
+    }
 
+    // write allow_full_table (whether query without filter can be rendered)
+    pub fn write_allow_full_table(&self, tokens: &mut TokenStream) {
+        if self.allow_full_table {
+            tokens.extend(quote! { true });
+        } else if let Some(field) = self.first_field(|x| x.allow_full_table) {
+            let ident = field.ident.as_ref().unwrap();
+            tokens.extend(quote! { self.#ident });
+        } else {
+            tokens.extend(quote! { false });
+        }
+    }
+
+    // write count (number of deleted rows is set to count field)
+    pub fn write_count(&self, tokens: &mut TokenStream) {
+        if let Some(field) = self.first_field(|x| x.count) {
+            let ident = field.ident.as_ref().unwrap();
             tokens.extend(quote! {
-                if let Some(clause) = self.#ident.get_limit::<DB>() {
-                    #target_string.push_str(" ");
-                    #target_string.push_str(&clause);
+                fn set_count(&mut self, count: u64) {
+                    self.#ident = ::std::convert::From::from(count as i64);
                 }
             });
         }
@@ -116,10 +170,9 @@ impl Builder {
 
     // write map implementation
     pub fn write_map(&self, tokens: &mut TokenStream) {
-        let ident = &self.ident;
         if let Some(path) = &self.map {
             tokens.extend(quote! {
-                let _fun: &dyn Fn(&mut #ident) -> buildix::Result<()> = &#path;
+                let _fun: &dyn Fn(&mut Self) -> buildix::Result<()> = &#path;
 
                 // call map function
                 let _ = #path(self)?;
@@ -137,22 +190,83 @@ impl quote::ToTokens for Builder {
         // all assertions (fields and other)
         let mut target = TokenStream::new();
 
-        // write `get_simple_query`
-        self.write_get_simple_query(&mut target);
-
         // prepare all assertions
+        let mut bounds = crate::generics::Bounds::default();
         for field in self.filter_fields(|_| true) {
-            field.write_assertions(&mut target);
+            field.write_assertions(&mut bounds, &self.generics);
+        }
+        target.extend(bounds.asserts.clone());
+        let (impl_generics, ty_generics, _) = self.generics.split_for_impl();
+        let where_clause = bounds.where_clause(&self.generics);
+
+        // check table and filter columns against schema
+        crate::schema::Schema::write_env_dependency(&mut target);
+        if let Some(schema) = crate::schema::Schema::load(&self.schema) {
+            if schema.table(&self.table).is_none() {
+                abort!(
+                    self.ident,
+                    crate::Error::InvalidSchema(format!("unknown table `{}`", self.table))
+                );
+            }
+            if !self.soft_delete.is_empty() {
+                let exists = schema
+                    .table(&self.table)
+                    .and_then(|table| table.column(&self.soft_delete));
+                if exists.is_none() {
+                    abort!(
+                        self.ident,
+                        crate::Error::InvalidSchema(format!(
+                            "unknown column `{}.{}` of `soft_delete`",
+                            self.table, self.soft_delete
+                        ))
+                    );
+                }
+            }
+            schema.write_dependency(&mut target);
+            for field in self.list_filter_fields() {
+                let ident = field.ident.as_ref().unwrap();
+                schema.write_filter_column(
+                    &mut target,
+                    ident,
+                    &field.ty,
+                    &self.generics,
+                    &self.table,
+                    &ident.to_string(),
+                );
+            }
         }
 
         let mut limit_impl = TokenStream::new();
+        let table = &self.table;
+
+        let mut returning_tokens = TokenStream::new();
+        if let Some(returning) = &self.returning {
+            returning_tokens.extend(quote! {
+                const RETURNING: Option<&'static str> = Some(#returning);
+            });
+        }
 
-        // prepare query ident (string to append values)
-        let final_query_ident = syn::Ident::new("query", Span::call_site());
+        // soft delete updates rows instead
+        let query_impl = if self.soft_delete.is_empty() {
+            quote! { ::buildix::delete::delete_query::<DB>(#table, clause.as_deref(), limit) }
+        } else {
+            let soft_delete = &self.soft_delete;
+            let flag = self.soft_delete_flag;
+            quote! { ::buildix::delete::soft_delete_query::<DB>(#table, #soft_delete, #flag, clause.as_deref(), limit) }
+        };
 
         // write filter now
-        self.write_filter(&mut target, &final_query_ident);
-        self.write_limit(&mut limit_impl, &final_query_ident);
+        self.write_filter(&mut target);
+        self.write_limit(&mut limit_impl);
+
+        let mut count_impl = TokenStream::new();
+        self.write_count(&mut count_impl);
+
+        let mut scope_impl = TokenStream::new();
+        self.write_scope(&mut scope_impl);
+
+        let mut allow_full_table_impl = TokenStream::new();
+        self.write_allow_full_table(&mut allow_full_table_impl);
 
         // now do map implementation
         let mut map_impl = TokenStream::new();
@@ -160,42 +274,67 @@ impl quote::ToTokens for Builder {
 
         // extend tokens with additional implementations
         tokens.extend(quote! {
-            use buildix::delete::DeleteBuilder as __DeleteBuilder;
-            use sqlx::Database;
-            use static_assertions;
-            use buildix::Filter as __Filter;
-            use buildix::limit::Limit as __Limit;
-
-            // add all assertions for compiler
-            #target
-
-            // implement DeleteBuilder
-            impl ::buildix::DeleteBuilder for #ident {
-
-                // generate sql along with arguments
-                fn to_sql<DB: Database>(&mut self) -> buildix::Result<(String, Vec<()>)> {
-
-                    // check map now
-
-                    // prepare query
-                    let mut query: String = self.get_simple_query().to_owned();
-
-                    // now process filter
-                    let fi = buildix::filter::FilterInfo::default();
-                    if let Some(filter_result) = self.process_filter::<DB>(&fi) {
-                        query.push_str(" WHERE ");
-                        query.push_str(&filter_result.clause);
+            const _: () = {
+                use buildix::delete::DeleteBuilder as __DeleteBuilder;
+                use ::buildix::dialect::Dialect;
+                use static_assertions;
+                use buildix::Filter as __Filter;
+                use buildix::limit::Limit as __Limit;
+
+                // add all assertions for compiler
+                #target
+
+                // implement DeleteBuilder
+                impl #impl_generics ::buildix::DeleteBuilder for #ident #ty_generics #where_clause {
+                    #returning_tokens
+
+                    // generate sql along with arguments (with `?` placeholders)
+                    fn to_raw_limited_sql<DB: Dialect>(&mut self, limit: Option<u64>) -> buildix::Result<(String, Vec<::buildix::value::Value>)> {
+
+                        // check map now
+
+                        let mut clauses: Vec<String> = vec![];
+                        let mut values: Vec<::buildix::value::Value> = vec![];
+
+                        // scope goes first
+                        #scope_impl
+
+                        // now process filter
+                        let fi = buildix::filter::FilterInfo::default();
+                        let mut filtered = false;
+                        if let Some(filter_result) = self.process_filter::<DB>(&fi) {
+                            if !filter_result.clause.is_empty() {
+                                filtered = true;
+                                clauses.push(filter_result.clause);
+                                values.extend(filter_result.values);
+                            }
+                        }
+
+                        // refuse to affect all rows (scope is not a filter)
+                        let allow_full_table: bool = #allow_full_table_impl;
+                        if !filtered && !allow_full_table {
+                            return Err(::buildix::Error::FullTable(#table.to_string()));
+                        }
+
+                        let clause: Option<String> = if clauses.is_empty() {
+                            None
+                        } else {
+                            Some(::buildix::filter::and(&clauses))
+                        };
+
+                        // limit is rendered by dialect
+                        let query = #query_impl;
+
+                        Ok((query, values))
                     }
 
-                    // now limit
                     #limit_impl
 
-                    Ok((query, vec![]))
+                    #count_impl
                 }
-            }
-
-            // Here comes execute
 
+                // Here comes execute
+            };
         });
     }
 }
@@ -208,13 +347,33 @@ impl quote::ToTokens for Builder {
 ///     * we have `table` set
 fn validate_builder(builder: Builder) -> Builder {
     let mut builder = builder;
-    builder.table = builder.table.trim().clone().to_string();
+    builder.table = builder.table.trim().to_string();
+    builder.soft_delete = builder.soft_delete.trim().to_string();
 
     // check if we have table set
     if builder.table.is_empty() {
         abort!(builder.ident, r#"Please provide `#[buildix(table=...)]`"#);
     }
 
+    // returned columns need to be listed
+    if let Some(returning) = builder.returning.as_mut() {
+        *returning = returning.trim().to_string();
+        if returning.is_empty() {
+            abort!(
+                builder.ident,
+                r#"Please provide columns of `#[buildix(returning=...)]`"#
+            );
+        }
+    }
+
+    // flag is kind of soft delete column
+    if builder.soft_delete_flag && builder.soft_delete.is_empty() {
+        abort!(
+            builder.ident,
+            r#"Please provide `#[buildix(soft_delete=...)]` column of `soft_delete_flag`"#
+        );
+    }
+
     // check if we have filter field - good practice to be sure that we don't have wild builders
     if builder.first_field(|x| x.filter).is_none() {
         abort!(
@@ -235,6 +394,19 @@ fn validate_builder(builder: Builder) -> Builder {
         crate::error::Error::MultipleFields("#[buildix(count)]".to_string()),
     );
 
+    builder.validate_single(
+        |x| x.allow_full_table,
+        crate::error::Error::MultipleFields("#[buildix(allow_full_table)]".to_string()),
+    );
+
+    // scope needs its value
+    builder.validate_single(
+        |x| x.scope,
+        crate::error::Error::MultipleFields("#[buildix(scope)]".to_string()),
+    );
+    let scope_field = builder.first_field(|x| x.scope).and_then(|f| f.ident.clone());
+    crate::select::validate_scope(&builder.ident, &mut builder.scope, scope_field.as_ref());
+
     builder
 }
 
@@ -255,6 +427,14 @@ pub struct BuilderField {
 
     #[darling(default)]
     limit: bool,
+
+    // value of scope
+    #[darling(default)]
+    scope: bool,
+
+    // runtime flag allowing query without filter
+    #[darling(default)]
+    allow_full_table: bool,
 }
 
 impl From<&BuilderField> for crate::filter::process::Field {
@@ -263,9 +443,13 @@ impl From<&BuilderField> for crate::filter::process::Field {
             ident: f.ident.as_ref().unwrap().clone(),
             ty: f.ty.clone(),
             expr: "".to_string(),
+            expr_span: Span::call_site(),
             table: "".to_string(),
             column: f.ident.as_ref().unwrap().to_string(),
             isnull: false,
+            matches: None,
+            ops: vec![],
+            ops_span: Span::call_site(),
         }
     }
 }
@@ -275,7 +459,10 @@ fn validate_field(f: BuilderField) -> BuilderField {
     // let mut f = f;
 
     // check if we have set at least one argument
-    if ![f.count, f.filter, f.limit].iter().any(|x| *x) {
+    if ![f.count, f.filter, f.limit, f.scope, f.allow_full_table]
+        .iter()
+        .any(|x| *x)
+    {
         abort!(f.ident.unwrap(), crate::Error::InvalidDelete);
     }
 
@@ -285,22 +472,19 @@ fn validate_field(f: BuilderField) -> BuilderField {
 // implement delete builder field
 impl BuilderField {
     // write assertions based on field
-    pub fn write_assertions(&self, tokens: &mut TokenStream) {
+    pub fn write_assertions(&self, bounds: &mut crate::generics::Bounds, generics: &syn::Generics) {
         let ty = &self.ty;
         if self.count {
-            tokens.extend(quote! {
-                static_assertions::assert_impl_all!(#ty: ::buildix::Count);
-            });
+            bounds.assert(ty, generics, quote! { ::buildix::Count });
         }
         if self.limit {
-            tokens.extend(quote! {
-                static_assertions::assert_impl_all!(#ty: ::buildix::Limit);
-            });
+            bounds.assert(ty, generics, quote! { ::buildix::Limit });
         }
         if self.filter {
-            tokens.extend(quote! {
-                static_assertions::assert_impl_all!(#ty: ::buildix::Filter);
-            });
+            bounds.assert(ty, generics, quote! { ::buildix::Filter });
+        }
+        if self.scope {
+            bounds.assert(ty, generics, quote! { ::buildix::scope::Scope });
         }
     }
 }
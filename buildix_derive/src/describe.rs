@@ -0,0 +1,133 @@
+// describe generates `buildix::describe::Describe` for filters and select builders with
+// `#[buildix(describe)]`, descriptions of parameters are taken from doc comments of fields.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+// doc returns doc comment of field (lines joined by space)
+pub fn doc(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(lit),
+                ..
+            })) => Some(lit.value().trim().to_string()),
+            _ => None,
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join(" "))
+    }
+}
+
+// Param is parameter of builder, value is described by type of field
+#[derive(Debug)]
+pub struct Param<'a> {
+    pub name: String,
+    pub ty: &'a syn::Type,
+    pub ops: Vec<String>,
+    pub description: Option<String>,
+    pub minimum: Option<i64>,
+
+    // parameters of nested filter are not prefixed by name
+    pub flatten: bool,
+}
+
+impl<'a> Param<'a> {
+    pub fn new(name: String, ty: &'a syn::Type) -> Self {
+        Self {
+            name,
+            ty,
+            ops: vec![],
+            description: None,
+            minimum: None,
+            flatten: false,
+        }
+    }
+}
+
+impl<'a> quote::ToTokens for Param<'a> {
+    // writes statement which adds parameter (or parameters of nested filter) to `params`
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.name;
+        let ty = self.ty;
+        // described operators are the listed ones supported by type (as checked by from_query)
+        let ops = if self.ops.is_empty() {
+            quote! { vec![] }
+        } else {
+            let ops = &self.ops;
+            quote! {
+                ::buildix::describe::supported_ops(
+                    <#ty as ::buildix::query::FromQuery>::OPS,
+                    &[#(#ops),*],
+                )
+            }
+        };
+        let description = match &self.description {
+            Some(description) => quote! { Some(#description.to_string()) },
+            None => quote! { None },
+        };
+        let minimum = match self.minimum {
+            Some(minimum) => quote! { Some(#minimum) },
+            None => quote! { None },
+        };
+
+        let nested_prefix = if self.flatten {
+            quote! { prefix.to_string() }
+        } else {
+            quote! { format!("{}.", name) }
+        };
+
+        tokens.extend(quote! {
+            let name = format!("{}{}", prefix, #name);
+            match <#ty as ::buildix::describe::Describe>::schema() {
+                Some(schema) => {
+                    let mut param = ::buildix::describe::Param::new(name, schema);
+                    param.ops = #ops;
+                    param.description = #description;
+                    param.minimum = #minimum;
+                    params.push(param);
+                }
+                None => params.extend(<#ty as ::buildix::describe::Describe>::params(&#nested_prefix)),
+            }
+        });
+    }
+}
+
+// write_describe implements Describe by parameters
+pub fn write_describe(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    params: &[Param],
+    tokens: &mut TokenStream,
+) {
+    let mut bounds = crate::generics::Bounds::default();
+    for param in params {
+        bounds.assert(param.ty, generics, quote! { ::buildix::describe::Describe });
+        if !param.ops.is_empty() {
+            bounds.assert(param.ty, generics, quote! { ::buildix::query::FromQuery });
+        }
+    }
+
+    let asserts = &bounds.asserts;
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let where_clause = bounds.where_clause(generics);
+
+    tokens.extend(quote! {
+        #asserts
+
+        impl #impl_generics ::buildix::describe::Describe for #ident #ty_generics #where_clause {
+            fn params(prefix: &str) -> Vec<::buildix::describe::Param> {
+                let mut params: Vec<::buildix::describe::Param> = vec![];
+                #(#params)*
+                params
+            }
+        }
+    });
+}
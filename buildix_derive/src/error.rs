@@ -4,11 +4,12 @@ use std::fmt::Debug;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
+#[allow(clippy::enum_variant_names)]
 pub enum Error {
-    #[error("Only buildix fields allowed: #[buildix(query)], #[buildix(filter)], #[buildix(offset)], #[buildix(limit)], #[buildix(count)], #[buildix(sort)], #[buildix(group)], #[buildix(having)]")]
+    #[error("Only buildix fields allowed: #[buildix(select)], #[buildix(filter)], #[buildix(offset)], #[buildix(limit)], #[buildix(count)], #[buildix(sort = \"...\")], #[buildix(group(...))], #[buildix(having)], #[buildix(distinct)], #[buildix(fields)], #[buildix(with_deleted)], #[buildix(scope)], #[buildix(with = \"...\")]")]
     InvalidColumn,
 
-    #[error("Please provide single #[buildix(query)] field")]
+    #[error("Please provide single #[buildix(select)] field")]
     MissingQuery,
 
     #[error("Please provide only single `{0}` field")]
@@ -20,11 +21,38 @@ pub enum Error {
     #[error("Invalid field: please provide either `table` or `expr` or none, but not both")]
     InvalidSelectField,
 
+    #[error("Invalid `with`: {0}")]
+    InvalidWith(String),
+
+    #[error("Invalid `aggregate`: {0}")]
+    InvalidAggregate(String),
+
+    #[error("Invalid `group`: {0}")]
+    InvalidGroup(String),
+
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(String),
+
+    #[error("Invalid sql: {0}")]
+    InvalidSql(String),
+
+    #[error("Invalid `flatten`: {0}")]
+    InvalidFlatten(String),
+
+    #[error("Invalid `distinct_on`: {0}")]
+    InvalidDistinctOn(String),
+
+    #[error("Invalid `with_deleted`: {0}")]
+    InvalidWithDeleted(String),
+
+    #[error("Invalid `scope`: {0}")]
+    InvalidScope(String),
+
     #[error("error")]
     Error,
 
     #[error(
-        "Only buildix fields allowed: #[buildix(filter)], #[buildix(count)], #[buildix(limit)]"
+        "Only buildix fields allowed: #[buildix(filter)], #[buildix(count)], #[buildix(limit)], #[buildix(scope)], #[buildix(allow_full_table)]"
     )]
     InvalidDelete,
 }
@@ -3,7 +3,9 @@
 #![warn(missing_debug_implementations)]
 
 pub mod process;
+pub mod query;
 
+use darling::util::SpannedValue;
 use darling::{self, ast, util, FromDeriveInput, FromField, FromMeta};
 use proc_macro2::TokenStream;
 use proc_macro_error::*;
@@ -19,6 +21,8 @@ use quote::quote;
 pub struct Filter {
     ident: syn::Ident,
 
+    generics: syn::Generics,
+
     #[darling(default)]
     expr: String,
 
@@ -34,6 +38,18 @@ pub struct Filter {
     // map function to validate filter
     #[darling(default)]
     map: Option<syn::Path>,
+
+    // DDL file to validate columns against
+    #[darling(default)]
+    schema: Option<SpannedValue<String>>,
+
+    // implement FromQuery (filter is set from url query string)
+    #[darling(default)]
+    from_query: bool,
+
+    // implement Describe (JSON schema of filter parameters)
+    #[darling(default)]
+    describe: bool,
 }
 
 // validate filter
@@ -72,22 +88,108 @@ impl quote::ToTokens for Filter {
             .unwrap()
             .fields
             .iter()
-            .map(|f| *f)
+            .copied()
             .map(|f| f.into())
             .collect();
 
+        let mut filter_tokens = TokenStream::new();
+
         // first do some compile time assertions
         for field in self.data.as_ref().take_struct().unwrap().fields {
             // write tokens
-            tokens.extend(quote! {
+            filter_tokens.extend(quote! {
                 #field
             });
         }
 
+        // check columns against schema
+        crate::schema::Schema::write_env_dependency(&mut filter_tokens);
+        if let Some(schema) = crate::schema::Schema::load(&self.schema) {
+            schema.write_dependency(&mut filter_tokens);
+            for field in self.data.as_ref().take_struct().unwrap().fields {
+                if !field.expr.is_empty() {
+                    continue;
+                }
+                let ident = field.ident.as_ref().unwrap();
+                let column = if field.column.is_empty() {
+                    ident.to_string()
+                } else {
+                    field.column.clone()
+                };
+                schema.write_filter_column(
+                    &mut filter_tokens,
+                    ident,
+                    &field.ty,
+                    &self.generics,
+                    &field.table,
+                    &column,
+                );
+            }
+        }
+
         // add map
 
+        // set from query string
+        if self.from_query {
+            query::write_from_query(&self.ident, &self.generics, &fields, &mut filter_tokens);
+        }
+
+        // describe parameters
+        if self.describe {
+            let params: Vec<crate::describe::Param> = self
+                .data
+                .as_ref()
+                .take_struct()
+                .unwrap()
+                .fields
+                .iter()
+                .map(|field| {
+                    let name = field.ident.as_ref().unwrap().to_string();
+                    let mut param = crate::describe::Param::new(name, &field.ty);
+                    param.ops = query::ops(&field.ops);
+                    param.description = crate::describe::doc(&field.attrs);
+                    param
+                })
+                .collect();
+            crate::describe::write_describe(&self.ident, &self.generics, &params, &mut filter_tokens);
+        }
+
         // process
-        process::process(&self.ident, fields, self.operator.0.clone(), tokens);
+        process::process(
+            &self.ident,
+            &self.generics,
+            fields,
+            self.operator.0.clone(),
+            &mut filter_tokens,
+        );
+
+        // in memory evaluation, filter without any value matches all rows
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, _) = self.generics.split_for_impl();
+        let predicates = crate::generics::Bounds::default().inline(&self.generics);
+        filter_tokens.extend(quote! {
+            impl #impl_generics #ident #ty_generics
+            where
+                #predicates
+                Self: ::buildix::filter::Filter,
+            {
+                // matches returns whether row matches filter
+                pub fn matches<R: ::buildix::filter::Fields + ?Sized>(&self, row: &R) -> bool {
+                    ::buildix::filter::Filter::match_filter(
+                        self,
+                        row,
+                        &::buildix::filter::FilterInfo::default(),
+                    )
+                    .unwrap_or(true)
+                }
+            }
+        });
+
+        tokens.extend(quote! {
+            const _: () = {
+                #filter_tokens
+            };
+        });
     }
 }
 
@@ -104,7 +206,7 @@ pub struct Field {
     pub ty: syn::Type,
 
     #[darling(default)]
-    pub expr: String,
+    pub expr: SpannedValue<String>,
 
     #[darling(default)]
     pub table: String,
@@ -114,6 +216,17 @@ pub struct Field {
 
     #[darling(default)]
     pub isnull: bool,
+
+    // function evaluating field in memory `fn(&T, &R) -> Option<bool>` (for custom expressions)
+    #[darling(default)]
+    pub matches: Option<syn::Path>,
+
+    // operator suffixes allowed in query string (`ops = "gt, lt"`)
+    #[darling(default)]
+    pub ops: SpannedValue<String>,
+
+    // passed attrs
+    pub attrs: Vec<syn::Attribute>,
 }
 
 impl quote::ToTokens for Field {
@@ -129,9 +242,15 @@ impl quote::ToTokens for Field {
 
 fn map_field(f: Field) -> Field {
     let mut f = f;
-    f.expr = f.expr.trim().to_string();
+    *f.expr = f.expr.trim().to_string();
     f.table = f.table.trim().to_string();
     f.column = f.column.trim().to_string();
 
+    if !f.expr.is_empty() {
+        if let Err(err) = crate::sql::check_expr(&f.expr) {
+            abort!(f.expr.span(), crate::Error::InvalidSql(err));
+        }
+    }
+
     f
 }
@@ -1,16 +1,24 @@
 use crate::error::Error;
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use proc_macro_error::*;
-use quote::quote;
+use quote::{quote, quote_spanned};
 
 #[derive(Debug)]
 pub struct Field {
     pub ident: syn::Ident,
     pub ty: syn::Type,
     pub expr: String,
+    pub expr_span: Span,
     pub table: String,
     pub column: String,
     pub isnull: bool,
+
+    // function evaluating field in memory (fields with custom expression)
+    pub matches: Option<syn::Path>,
+
+    // operator suffixes allowed in query string
+    pub ops: Vec<String>,
+    pub ops_span: Span,
 }
 
 impl Field {
@@ -18,21 +26,33 @@ impl Field {
         let mut expr = self.expr.trim().to_string();
 
         if expr.is_empty() {
-            let mut ident = self.ident.to_string();
-            if !self.column.is_empty() {
-                ident = self.column.clone();
-            }
+            expr = format!("{} = ?", self.get_column());
+        }
 
-            if !self.table.is_empty() {
-                ident = format!("{}.{}", self.table, ident);
-            }
+        expr
+    }
 
-            if self.expr.is_empty() {
-                expr = format!("{} = ?", ident);
-            }
+    // get_column returns column rendered in sql (qualified by table)
+    pub fn get_column(&self) -> String {
+        let mut column = self.ident.to_string();
+        if !self.column.is_empty() {
+            column = self.column.clone();
         }
 
-        expr
+        if !self.table.is_empty() {
+            column = format!("{}.{}", self.table, column);
+        }
+
+        column
+    }
+
+    // get_name returns name of row field which is filtered (when evaluated in memory)
+    pub fn get_name(&self) -> String {
+        if self.column.is_empty() {
+            self.ident.to_string()
+        } else {
+            self.column.clone()
+        }
     }
 }
 
@@ -41,10 +61,14 @@ impl From<&crate::filter::Field> for Field {
         Self {
             ident: out.ident.clone().unwrap(),
             ty: out.ty.clone(),
-            expr: out.expr.clone(),
+            expr: out.expr.to_string(),
+            expr_span: out.expr.span(),
             isnull: out.isnull,
             table: out.table.clone(),
             column: out.column.clone(),
+            matches: out.matches.clone(),
+            ops: super::query::ops(&out.ops),
+            ops_span: out.ops.span(),
         }
     }
 }
@@ -55,49 +79,95 @@ impl From<&crate::select::field::Field> for Field {
             ident: out.ident.clone().unwrap(),
             ty: out.ty.clone(),
             expr: "".to_string(),
+            expr_span: Span::call_site(),
             isnull: false,
             table: "".to_string(),
             column: "".to_string(),
+            matches: None,
+            ops: vec![],
+            ops_span: Span::call_site(),
         }
     }
 }
 
 // process fields and write implementation
-pub fn process(ident: &syn::Ident, fields: Vec<Field>, operator: String, tokens: &mut TokenStream) {
-    let mut field_asserts = TokenStream::new();
+pub fn process(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    fields: Vec<Field>,
+    operator: String,
+    tokens: &mut TokenStream,
+) {
+    let mut bounds = crate::generics::Bounds::default();
     let mut field_impl = TokenStream::new();
+    let mut match_impl = TokenStream::new();
 
     for field in &fields {
         let field_type = &field.ty;
         let field_ident = &field.ident;
         let field_ident_str = field_ident.to_string();
         let field_expr = field.get_expr();
-        let has_field_expr = !field_expr.is_empty();
+        let field_column = field.get_column();
+        // only explicit expression is passed, so filters render their own default one
+        let has_field_expr = !field.expr.trim().is_empty();
 
-        field_asserts.extend(quote! {
-            static_assertions::assert_impl_all!(#field_type: ::buildix::filter::Filter);
-        });
+        bounds.assert(field_type, generics, quote! { ::buildix::filter::Filter });
 
-        // check if this field can be nullable
-        if field.isnull {
-            field_asserts.extend(quote! {
-                static_assertions::assert_impl_all!(#field_type: ::buildix::filter::Nullable);
+        // explicit expression has to have as many placeholders as field type binds values
+        let static_type = crate::generics::static_type(field_type, generics);
+        if let (false, Some(static_type)) = (field.expr.is_empty(), static_type) {
+            let placeholders = match crate::sql::placeholders(&field.expr) {
+                Ok(placeholders) => placeholders,
+                Err(err) => abort!(field.expr_span, Error::InvalidSql(err)),
+            };
+            let message = format!(
+                "expression `{}` of field `{}` has {} placeholder(s), but field binds different number of values",
+                field.expr, field_ident_str, placeholders
+            );
+            bounds.asserts.extend(quote_spanned! {field.expr_span=>
+                const _: () = assert!(
+                    match <#static_type as ::buildix::filter::Filter>::VALUES {
+                        Some(values) => values == #placeholders,
+                        None => true,
+                    },
+                    #message
+                );
             });
         }
 
-        let mut expr_tokens = TokenStream::new();
-        if has_field_expr {
-            expr_tokens.extend(quote! {
-                filter_info.expr = Some(#field_expr.to_string());
+        // operators are checked against the ones supported by runtime
+        for op in &field.ops {
+            let message = format!(
+                "unknown operator `{}` of field `{}` (supported are listed by buildix::query::OPS)",
+                op, field_ident_str
+            );
+            bounds.asserts.extend(quote_spanned! {field.ops_span=>
+                const _: () = assert!(::buildix::query::is_op(#op), #message);
             });
         }
 
+        // check if this field can be nullable
+        if field.isnull {
+            bounds.assert(field_type, generics, quote! { ::buildix::filter::Nullable });
+        }
+
+        let expr_tokens = if has_field_expr {
+            quote! {
+                filter_info.expr = Some(#field_expr.to_string());
+            }
+        } else {
+            quote! {
+                filter_info.expr = None;
+            }
+        };
+
         let isnull = field.isnull;
 
         // add actual implementation
         field_impl.extend(quote! {
             // set values
             filter_info.ident = #field_ident_str;
+            filter_info.column = #field_column;
             filter_info.isnull = #isnull;
 
             #expr_tokens
@@ -112,29 +182,62 @@ pub fn process(ident: &syn::Ident, fields: Vec<Field>, operator: String, tokens:
                 filter_clauses.push(filter_result.clause.clone());
             };
         });
+
+        // in memory evaluation (custom expressions are evaluated by `matches` function only)
+        let field_name = field.get_name();
+        let has_custom_expr = !field.expr.trim().is_empty();
+        match &field.matches {
+            Some(path) => match_impl.extend(quote! {
+                if let Some(result) = #path(&self.#field_ident, row) {
+                    match_results.push(result);
+                }
+            }),
+            // custom expression without function cannot be evaluated, so set field never matches
+            // (row which query would reject must not be matched)
+            None if has_custom_expr => match_impl.extend(quote! {
+                filter_info.ident = #field_ident_str;
+                filter_info.isnull = #isnull;
+                filter_info.expr = Some(#field_expr.to_string());
+
+                if self.#field_ident.process_filter::<::buildix::dialect::Postgres>(&filter_info).is_some() {
+                    match_results.push(false);
+                }
+            }),
+            None => match_impl.extend(quote! {
+                filter_info.ident = #field_name;
+                filter_info.isnull = #isnull;
+                filter_info.expr = None;
+
+                if let Some(result) = self.#field_ident.match_filter(row, &filter_info) {
+                    match_results.push(result);
+                }
+            }),
+        }
     }
 
+    let any = operator.trim().eq_ignore_ascii_case("or");
+
     let operator = format!(" {} ", operator);
+    let field_asserts = &bounds.asserts;
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let where_clause = bounds.where_clause(generics);
 
     // generate filter stuff
     tokens.extend(quote! {
-        use buildix::prelude::*;
-
         // field assertions first
         #field_asserts
 
         // filter implementation
-        impl ::buildix::filter::Filter for #ident {
+        impl #impl_generics ::buildix::filter::Filter for #ident #ty_generics #where_clause {
 
             // process_filter returns all values
-            fn process_filter<DB: Database>(&self, info: &::buildix::filter::FilterInfo) -> Option<::buildix::filter::FilterResult> {
-                let mut filter_values: Vec<()> = vec![];
+            fn process_filter<DB: ::buildix::dialect::Dialect>(&self, info: &::buildix::filter::FilterInfo) -> Option<::buildix::filter::FilterResult> {
+                use ::buildix::filter::Filter as _;
+
+                let mut filter_values: Vec<::buildix::value::Value> = vec![];
                 let mut filter_clauses: Vec<String> = vec![];
                 let mut filter_info = ::buildix::filter::FilterInfo::default();
 
-                // prepare
-                let _example_values: Vec<DB> = vec![];
-
                 #field_impl
 
                 // check for clauses
@@ -152,6 +255,24 @@ pub fn process(ident: &syn::Ident, fields: Vec<Field>, operator: String, tokens:
                     Some(::buildix::filter::FilterResult::new(clause, filter_values, len))
                 }
             }
+
+            // match_filter evaluates the same logic against row in memory
+            fn match_filter<R: ::buildix::filter::Fields + ?Sized>(&self, row: &R, info: &::buildix::filter::FilterInfo) -> Option<bool> {
+                use ::buildix::filter::Filter as _;
+
+                let mut match_results: Vec<bool> = vec![];
+                let mut filter_info = ::buildix::filter::FilterInfo::default();
+
+                #match_impl
+
+                if match_results.is_empty() {
+                    None
+                } else if #any {
+                    Some(match_results.iter().any(|r| *r))
+                } else {
+                    Some(match_results.iter().all(|r| *r))
+                }
+            }
         }
     });
 }
@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+
+use super::process::Field;
+
+// ops returns list of operators from attribute value (`gt, lt`)
+pub fn ops(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|op| op.trim().to_lowercase())
+        .filter(|op| !op.is_empty())
+        .collect()
+}
+
+// write_from_query implements FromQuery, parameters are named by fields and operators are allowed
+// only when listed by field, except of `eq` (nested filters check their own fields)
+pub fn write_from_query(
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+    fields: &[Field],
+    tokens: &mut TokenStream,
+) {
+    let mut bounds = crate::generics::Bounds::default();
+    let mut arms = TokenStream::new();
+
+    for field in fields {
+        let field_ident = &field.ident;
+        let name = field_ident.to_string();
+        let ops = &field.ops;
+
+        bounds.assert(&field.ty, generics, quote! { ::buildix::query::FromQuery });
+
+        // operators have to be supported by type of field (`eq` always is, unknown ones are
+        // reported by filter)
+        if let Some(static_type) = crate::generics::static_type(&field.ty, generics) {
+            for op in ops.iter().filter(|op| *op != "eq") {
+                let message = format!(
+                    "operator `{}` is not supported by type of field `{}`",
+                    op, name
+                );
+                bounds.asserts.extend(quote_spanned! {field.ops_span=>
+                    const _: () = assert!(
+                        !::buildix::query::is_op(#op)
+                            || ::buildix::query::has_op(
+                                <#static_type as ::buildix::query::FromQuery>::OPS,
+                                #op,
+                            ),
+                        #message
+                    );
+                });
+            }
+        }
+
+        arms.extend(quote! {
+            #name => {
+                let ops: &[&str] = &[#(#ops),*];
+                match op {
+                    Some(op) if rest.is_empty() && op != "eq" && !ops.contains(&op) => {
+                        Err(format!("operator `{}` is not allowed", op))
+                    }
+                    _ => ::buildix::query::FromQuery::set_param(&mut self.#field_ident, rest, op, value),
+                }
+            }
+        });
+    }
+
+    let asserts = &bounds.asserts;
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let where_clause = bounds.where_clause(generics);
+
+    tokens.extend(quote! {
+        #asserts
+
+        impl #impl_generics ::buildix::query::FromQuery for #ident #ty_generics #where_clause {
+            fn set_param(&mut self, path: &str, op: Option<&str>, value: &str) -> Result<(), String> {
+                // first part of path is field, rest addresses field of nested filter
+                let (name, rest) = path.split_once('.').unwrap_or((path, ""));
+                match name {
+                    #arms
+                    "" => Err("missing field".to_string()),
+                    _ => Err(format!("unknown field `{}`", name)),
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,127 @@
+// generics helps derives with generic (and lifetime-parameterized) structs. Field types are
+// asserted by static assertions when possible, types which use type parameters are asserted by
+// bounds in where clause of implementation instead.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::visit_mut::VisitMut;
+
+// StaticType replaces lifetimes of struct with 'static and finds usage of type parameters
+struct StaticType<'a> {
+    generics: &'a syn::Generics,
+    has_type_param: bool,
+}
+
+impl<'a> VisitMut for StaticType<'a> {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if self.generics.lifetimes().any(|l| l.lifetime == *lifetime) {
+            *lifetime = syn::Lifetime::new("'static", lifetime.span());
+        }
+    }
+
+    fn visit_type_path_mut(&mut self, path: &mut syn::TypePath) {
+        if path.qself.is_none() {
+            if let Some(first) = path.path.segments.first() {
+                let ident = &first.ident;
+                if self.generics.type_params().any(|t| t.ident == *ident)
+                    || self.generics.const_params().any(|c| c.ident == *ident)
+                {
+                    self.has_type_param = true;
+                }
+            }
+        }
+        syn::visit_mut::visit_type_path_mut(self, path);
+    }
+
+    fn visit_expr_path_mut(&mut self, path: &mut syn::ExprPath) {
+        if let Some(first) = path.path.segments.first() {
+            if self.generics.const_params().any(|c| c.ident == first.ident) {
+                self.has_type_param = true;
+            }
+        }
+        syn::visit_mut::visit_expr_path_mut(self, path);
+    }
+}
+
+// static_type returns type with lifetimes of struct replaced by 'static, so it can be used
+// outside of implementation. None is returned when type uses type parameters.
+pub fn static_type(ty: &syn::Type, generics: &syn::Generics) -> Option<syn::Type> {
+    let mut ty = ty.clone();
+    let mut visitor = StaticType {
+        generics,
+        has_type_param: false,
+    };
+    visitor.visit_type_mut(&mut ty);
+
+    if visitor.has_type_param {
+        None
+    } else {
+        Some(ty)
+    }
+}
+
+// prepend returns generics of struct with additional parameters of implementation (lifetimes
+// go first)
+pub fn prepend(generics: &syn::Generics, params: Vec<syn::GenericParam>) -> syn::Generics {
+    let mut result = generics.clone();
+    for (i, param) in params.into_iter().enumerate() {
+        result.params.insert(i, param);
+    }
+    result
+}
+
+// Bounds collects assertions of field types (static) and bounds of where clause
+#[derive(Debug, Default)]
+pub struct Bounds {
+    pub asserts: TokenStream,
+    pub predicates: Vec<TokenStream>,
+}
+
+impl Bounds {
+    // assert asserts that field type implements traits
+    pub fn assert(&mut self, ty: &syn::Type, generics: &syn::Generics, traits: TokenStream) {
+        match static_type(ty, generics) {
+            Some(ty) => self.asserts.extend(quote! {
+                static_assertions::assert_impl_all!(#ty: #traits);
+            }),
+            None => self.predicates.push(quote! {
+                #ty: #traits
+            }),
+        }
+    }
+
+    // where_clause returns where clause of struct extended by bounds
+    pub fn where_clause(&self, generics: &syn::Generics) -> TokenStream {
+        let predicates = &self.predicates;
+        let existing: Vec<&syn::WherePredicate> = generics
+            .where_clause
+            .as_ref()
+            .map(|w| w.predicates.iter().collect())
+            .unwrap_or_default();
+
+        if existing.is_empty() && predicates.is_empty() {
+            return TokenStream::new();
+        }
+
+        quote! {
+            where
+                #(#existing,)*
+                #(#predicates,)*
+        }
+    }
+
+    // predicates returns bounds (along with bounds of struct) to be used in existing where clause
+    pub fn inline(&self, generics: &syn::Generics) -> TokenStream {
+        let predicates = &self.predicates;
+        let existing: Vec<&syn::WherePredicate> = generics
+            .where_clause
+            .as_ref()
+            .map(|w| w.predicates.iter().collect())
+            .unwrap_or_default();
+
+        quote! {
+            #(#existing,)*
+            #(#predicates,)*
+        }
+    }
+}
@@ -1,20 +1,26 @@
 #[macro_use]
 mod select;
 mod delete;
+mod describe;
 mod error;
+mod schema;
+mod sql;
 mod filter;
+mod generics;
 
 use error::Error;
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
-use proc_macro_roids::DeriveInputExt;
 use quote::quote;
 
 #[proc_macro_derive(DeleteBuilder, attributes(buildix))]
 #[proc_macro_error]
 pub fn derive_delete_builder(input: TokenStream) -> TokenStream {
     let input: syn::DeriveInput = syn::parse_macro_input!(input);
-    let builder: delete::Builder = darling::FromDeriveInput::from_derive_input(&input).unwrap();
+    let builder: delete::Builder = match darling::FromDeriveInput::from_derive_input(&input) {
+        Ok(builder) => builder,
+        Err(err) => return err.write_errors().into(),
+    };
 
     // prepare new tokens
     let mut toks = proc_macro2::TokenStream::new();
@@ -26,7 +32,10 @@ pub fn derive_delete_builder(input: TokenStream) -> TokenStream {
 #[proc_macro_error]
 pub fn derive_filter(input: TokenStream) -> TokenStream {
     let input: syn::DeriveInput = syn::parse_macro_input!(input);
-    let filter: filter::Filter = darling::FromDeriveInput::from_derive_input(&input).unwrap();
+    let filter: filter::Filter = match darling::FromDeriveInput::from_derive_input(&input) {
+        Ok(filter) => filter,
+        Err(err) => return err.write_errors().into(),
+    };
 
     // prepare new tokens
     let mut toks = proc_macro2::TokenStream::new();
@@ -38,7 +47,10 @@ pub fn derive_filter(input: TokenStream) -> TokenStream {
 #[proc_macro_error]
 pub fn derive_select(input: TokenStream) -> TokenStream {
     let input: syn::DeriveInput = syn::parse_macro_input!(input);
-    let q: select::Select = darling::FromDeriveInput::from_derive_input(&input).unwrap();
+    let q: select::Select = match darling::FromDeriveInput::from_derive_input(&input) {
+        Ok(q) => q,
+        Err(err) => return err.write_errors().into(),
+    };
 
     let mut toks = proc_macro2::TokenStream::new();
     toks.extend(quote! {#q});
@@ -48,14 +60,13 @@ pub fn derive_select(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(SelectBuilder, attributes(buildix))]
 #[proc_macro_error]
 pub fn derive_select_builder(input: TokenStream) -> TokenStream {
-    let mut input: syn::DeriveInput = syn::parse_macro_input!(input);
-
-    // add derive from sqlx::FromRow
-    let derives = syn::parse_quote!(sqlx::FromRow);
-    input.append_derives(derives);
+    let input: syn::DeriveInput = syn::parse_macro_input!(input);
 
     // parse builder
-    let sel: select::SelectBuilder = darling::FromDeriveInput::from_derive_input(&input).unwrap();
+    let sel: select::SelectBuilder = match darling::FromDeriveInput::from_derive_input(&input) {
+        Ok(sel) => sel,
+        Err(err) => return err.write_errors().into(),
+    };
 
     // prepare new tokens
     let mut toks = proc_macro2::TokenStream::new();
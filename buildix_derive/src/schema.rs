@@ -0,0 +1,222 @@
+// schema validates tables and columns referenced by derives against checked-in DDL file.
+// Schema is opt-in by `#[buildix(schema = "schema.sql")]` or `BUILDIX_SCHEMA` environment
+// variable, path is relative to crate manifest directory and can reference environment variables
+// (`${NAME}/schema.sql`). Environment variables are tracked by generated code, so crate is rebuilt
+// when they change.
+
+use darling::util::SpannedValue;
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::*;
+use quote::quote;
+use sqlparser::ast::{ColumnOption, Statement, TableConstraint};
+use sqlparser::parser::Parser;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+// environment variable with path to schema file
+pub const SCHEMA_ENV: &str = "BUILDIX_SCHEMA";
+
+#[derive(Debug, Default)]
+pub struct Schema {
+    // path of schema file
+    pub path: PathBuf,
+
+    // environment variables referenced by path
+    env: Vec<String>,
+
+    // tables by lowercase name
+    tables: HashMap<String, Table>,
+}
+
+#[derive(Debug, Default)]
+pub struct Table {
+    // columns by lowercase name, value is whether column is nullable
+    columns: HashMap<String, bool>,
+}
+
+thread_local! {
+    // parsed schemas by path, so every derive does not parse file again
+    static SCHEMAS: RefCell<HashMap<PathBuf, Rc<Schema>>> = RefCell::new(HashMap::new());
+}
+
+impl Schema {
+    // load returns schema by attribute (or environment variable), None when schema is not used
+    pub fn load(attr: &Option<SpannedValue<String>>) -> Option<Rc<Schema>> {
+        let (path, span) = match attr {
+            Some(path) => (path.trim().to_string(), path.span()),
+            None => (std::env::var(SCHEMA_ENV).ok()?, Span::call_site()),
+        };
+        if path.is_empty() {
+            return None;
+        }
+
+        let (path, env) = match expand(&path) {
+            Ok(expanded) => expanded,
+            Err(err) => abort!(span, crate::Error::InvalidSchema(err)),
+        };
+
+        let mut full = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+        full.push(&path);
+
+        if let Some(schema) = SCHEMAS.with(|s| s.borrow().get(&full).cloned()) {
+            return Some(schema);
+        }
+
+        let content = match std::fs::read_to_string(&full) {
+            Ok(content) => content,
+            Err(err) => abort!(
+                span,
+                crate::Error::InvalidSchema(format!("cannot read `{}`: {}", full.display(), err))
+            ),
+        };
+
+        let statements = match Parser::parse_sql(crate::sql::dialect().as_ref(), &content) {
+            Ok(statements) => statements,
+            Err(err) => abort!(
+                span,
+                crate::Error::InvalidSchema(format!("cannot parse `{}`: {}", path, err))
+            ),
+        };
+
+        let mut schema = Schema {
+            path: full.clone(),
+            env,
+            ..Default::default()
+        };
+
+        for statement in statements {
+            if let Statement::CreateTable(create) = statement {
+                let mut table = Table::default();
+
+                // primary key columns are never null
+                let mut primary: Vec<String> = vec![];
+                for constraint in &create.constraints {
+                    if let TableConstraint::PrimaryKey { columns, .. } = constraint {
+                        primary.extend(columns.iter().map(|c| c.value.to_lowercase()));
+                    }
+                }
+
+                for column in &create.columns {
+                    let name = column.name.value.to_lowercase();
+                    let not_null = primary.contains(&name)
+                        || column.options.iter().any(|o| {
+                            matches!(
+                                o.option,
+                                ColumnOption::NotNull | ColumnOption::Unique { is_primary: true, .. }
+                            )
+                        });
+                    table.columns.insert(name, !not_null);
+                }
+
+                let name = create.name.0.last().unwrap().value.to_lowercase();
+                schema.tables.insert(name, table);
+            }
+        }
+
+        let schema = Rc::new(schema);
+        SCHEMAS.with(|s| s.borrow_mut().insert(full, schema.clone()));
+        Some(schema)
+    }
+
+    // table returns table by name
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.get(&unquote(name))
+    }
+
+    // has_column returns whether any table has column
+    pub fn has_column(&self, column: &str) -> bool {
+        self.tables.values().any(|t| t.column(column).is_some())
+    }
+
+    // write_filter_column writes assertion for filter field which is not in schema. Assertion only
+    // fails when filter type uses field as column (nested filters don't).
+    pub fn write_filter_column(
+        &self,
+        tokens: &mut TokenStream,
+        ident: &syn::Ident,
+        ty: &syn::Type,
+        generics: &syn::Generics,
+        table: &str,
+        column: &str,
+    ) {
+        // types with type parameters cannot be checked outside of implementation
+        let ty = match crate::generics::static_type(ty, generics) {
+            Some(ty) => ty,
+            None => return,
+        };
+        let exists = match self.table(table) {
+            Some(table) => table.column(column).is_some(),
+            None => self.has_column(column),
+        };
+        if exists {
+            return;
+        }
+
+        let message = if table.is_empty() {
+            format!("unknown column `{}` of field `{}` in schema", column, ident)
+        } else {
+            format!("unknown column `{}.{}` of field `{}` in schema", table, column, ident)
+        };
+        tokens.extend(quote::quote_spanned! {ident.span()=>
+            const _: () = assert!(!<#ty as ::buildix::filter::Filter>::COLUMN, #message);
+        });
+    }
+
+    // write_dependency writes `include_bytes!` of schema file and `option_env!` of variables
+    // referenced by path, so crate is rebuilt on change
+    pub fn write_dependency(&self, tokens: &mut TokenStream) {
+        let path = self.path.display().to_string();
+        let env = &self.env;
+        tokens.extend(quote! {
+            const _: &[u8] = include_bytes!(#path);
+            #(const _: Option<&str> = option_env!(#env);)*
+        });
+    }
+
+    // write_env_dependency writes `option_env!` of schema variable, so crate is rebuilt when it
+    // is set, changed or removed
+    pub fn write_env_dependency(tokens: &mut TokenStream) {
+        tokens.extend(quote! {
+            const _: Option<&str> = option_env!(#SCHEMA_ENV);
+        });
+    }
+}
+
+impl Table {
+    // column returns whether column is nullable (None when column does not exist)
+    pub fn column(&self, name: &str) -> Option<bool> {
+        self.columns.get(&unquote(name)).copied()
+    }
+}
+
+// expand replaces `${NAME}` in path by value of environment variable, names of referenced
+// variables are returned along with path
+fn expand(path: &str) -> Result<(String, Vec<String>), String> {
+    let mut expanded = String::with_capacity(path.len());
+    let mut env = vec![];
+    let mut rest = path;
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => return Err(format!("unclosed `${{` in `{}`", path)),
+        };
+        let name = &rest[start + 2..end];
+        let value = std::env::var(name)
+            .map_err(|_| format!("environment variable `{}` of `{}` is not set", name, path))?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&value);
+        env.push(name.to_string());
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok((expanded, env))
+}
+
+// unquote returns lowercase name without quotes
+fn unquote(name: &str) -> String {
+    name.trim()
+        .trim_matches(|c| c == '"' || c == '`' || c == '[' || c == ']')
+        .to_lowercase()
+}
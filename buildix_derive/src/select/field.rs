@@ -1,4 +1,5 @@
-use darling::FromField;
+use darling::util::SpannedValue;
+use darling::{FromField, FromMeta};
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::fmt::{Debug, Display};
@@ -31,10 +32,31 @@ pub struct Field {
     pub(crate) sort: Option<String>,
 
     #[darling(default)]
-    pub(crate) group: bool,
+    pub(crate) group: Option<Group>,
 
     #[darling(default)]
     pub(crate) having: bool,
+
+    #[darling(default)]
+    pub(crate) distinct: bool,
+
+    #[darling(default)]
+    pub(crate) fields: bool,
+
+    // soft deleted rows are included when field is true
+    #[darling(default)]
+    pub(crate) with_deleted: bool,
+
+    // value of scope
+    #[darling(default)]
+    pub(crate) scope: bool,
+
+    // builder of common table expression with given name (instead of default one)
+    #[darling(default)]
+    pub(crate) with: Option<String>,
+
+    // passed attrs
+    pub(crate) attrs: Vec<syn::Attribute>,
 }
 
 impl Field {
@@ -46,14 +68,108 @@ impl Field {
             self.limit,
             self.count,
             self.sort.is_some(),
-            self.group,
+            self.group.is_some(),
             self.having,
+            self.distinct,
+            self.fields,
+            self.with_deleted,
+            self.scope,
+            self.with.is_some(),
         ]
         .iter()
         .any(|x| *x)
         {
             return Err(crate::Error::InvalidColumn);
         }
+        if let Some(group) = self.get_group() {
+            group.validate()?;
+        }
+        // with_deleted is flag
+        if self.with_deleted && !is_bool(&self.ty) {
+            return Err(crate::Error::InvalidWithDeleted(
+                "field must be `bool`".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    // get_group returns grouping keys of field (None for bare `#[buildix(group)]` flag)
+    pub fn get_group(&self) -> Option<&GroupAttribute> {
+        self.group.as_ref().and_then(|g| g.0.as_ref())
+    }
+}
+
+// is_bool returns whether type is plain `bool`
+fn is_bool(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path.qself.is_none() && path.path.is_ident("bool"),
+        _ => false,
+    }
+}
+
+// Group is `#[buildix(group)]` attribute, either bare flag or list of grouping keys
+#[derive(Clone, Debug, Default)]
+pub struct Group(pub(crate) Option<GroupAttribute>);
+
+impl FromMeta for Group {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Group(None))
+    }
+
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        GroupAttribute::from_list(items).map(|g| Group(Some(g)))
+    }
+}
+
+// GroupAttribute provides whitelisted grouping keys, which are chosen by field value at runtime
+#[derive(Clone, Debug, Default, FromMeta)]
+pub struct GroupAttribute {
+    #[darling(default, multiple, rename = "key")]
+    pub(crate) keys: Vec<GroupKeyAttribute>,
+
+    // select field which is selected by expression of grouping key
+    #[darling(default)]
+    pub(crate) select: String,
+
+    // extend static group of select (by default entry of `select` field is replaced)
+    #[darling(default)]
+    pub(crate) extend: bool,
+}
+
+#[derive(Clone, Debug, FromMeta)]
+pub struct GroupKeyAttribute {
+    pub(crate) name: String,
+
+    pub(crate) expr: SpannedValue<String>,
+}
+
+impl GroupAttribute {
+    // validate checks that keys are provided and have unique names, and that replaced field is
+    // known
+    pub fn validate(&self) -> Result<(), crate::Error> {
+        if self.keys.is_empty() {
+            return Err(crate::Error::InvalidGroup(
+                "please provide at least one `key(name = ..., expr = ...)`".to_string(),
+            ));
+        }
+        if self.select.trim().is_empty() && !self.extend {
+            return Err(crate::Error::InvalidGroup(
+                "please provide `select` field whose group entry is replaced, or `extend`".to_string(),
+            ));
+        }
+        for (i, key) in self.keys.iter().enumerate() {
+            if key.name.trim().is_empty() || key.expr.trim().is_empty() {
+                return Err(crate::Error::InvalidGroup(
+                    "key needs both `name` and `expr`".to_string(),
+                ));
+            }
+            if self.keys[..i].iter().any(|k| k.name.trim() == key.name.trim()) {
+                return Err(crate::Error::InvalidGroup(format!(
+                    "duplicate key `{}`",
+                    key.name.trim()
+                )));
+            }
+        }
         Ok(())
     }
 }
@@ -3,6 +3,7 @@
 #![allow(dead_code)]
 
 pub mod field;
+#[allow(clippy::module_inception)]
 mod select;
 
 pub use select::Select;
@@ -10,7 +11,7 @@ pub use select::Select;
 use darling::{self, ast, util, FromDeriveInput, FromField, FromMeta, FromVariant};
 use proc_macro2::{Span, TokenStream};
 use proc_macro_error::*;
-use quote::quote;
+use quote::{quote, quote_spanned};
 use std::fmt::{Debug, Formatter};
 
 #[derive(Debug, FromDeriveInput)]
@@ -24,6 +25,8 @@ pub struct SelectBuilder {
     // indent
     ident: syn::Ident,
 
+    generics: syn::Generics,
+
     // data
     data: ast::Data<util::Ignored, field::Field>,
 
@@ -33,6 +36,18 @@ pub struct SelectBuilder {
 
     #[darling(default)]
     error: Option<syn::Ident>,
+
+    // postgres `DISTINCT ON (...)` expressions
+    #[darling(default)]
+    distinct_on: String,
+
+    // implement Describe (JSON schema of filters, sorts, limit and offset)
+    #[darling(default)]
+    describe: bool,
+
+    // column which scopes every query (value is provided by `#[buildix(scope)]` field)
+    #[darling(default)]
+    scope: Option<String>,
 }
 
 // TODO: this is not working because of some duplicates
@@ -55,8 +70,7 @@ impl SelectBuilder {
             .fields
             .iter()
             .filter(|x| call(x))
-            .skip(1)
-            .next()
+            .nth(1)
         {
             abort!(invalid.ident, err);
         };
@@ -71,7 +85,7 @@ impl SelectBuilder {
             .unwrap()
             .fields
             .iter()
-            .map(|x| *x)
+            .copied()
             .filter(|x| call(x))
             .collect()
     }
@@ -81,7 +95,7 @@ impl SelectBuilder {
     where
         T: Fn(&field::Field) -> bool,
     {
-        self.filter_fields(|x| call(x)).iter().map(|x| *x).next()
+        self.filter_fields(|x| call(x)).first().copied()
     }
 
     // we are totally sure that we have at least one field
@@ -101,7 +115,41 @@ impl SelectBuilder {
 
     // get group field
     pub fn get_group_field(&self) -> Option<&field::Field> {
-        self.get_first_field(|x| x.group)
+        self.get_first_field(|x| x.group.is_some())
+    }
+
+    // get fields (projection) field
+    pub fn get_fields_field(&self) -> Option<&field::Field> {
+        self.get_first_field(|x| x.fields)
+    }
+
+    // get distinct field
+    pub fn get_distinct_field(&self) -> Option<&field::Field> {
+        self.get_first_field(|x| x.distinct)
+    }
+
+    // get with_deleted field
+    pub fn get_with_deleted_field(&self) -> Option<&field::Field> {
+        self.get_first_field(|x| x.with_deleted)
+    }
+
+    // get scope field
+    pub fn get_scope_field(&self) -> Option<&field::Field> {
+        self.get_first_field(|x| x.scope)
+    }
+
+    // get distinct on expressions
+    pub fn get_distinct_on(&self) -> Vec<String> {
+        self.distinct_on
+            .split(',')
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty())
+            .collect()
+    }
+
+    // get fields providing builders of common table expressions
+    pub fn get_with_fields(&self) -> Vec<&field::Field> {
+        self.filter_fields(|x| x.with.is_some())
     }
 
     // get sort field
@@ -118,6 +166,41 @@ impl SelectBuilder {
     pub fn get_having_fields(&self) -> Vec<&field::Field> {
         self.filter_fields(|x| x.having)
     }
+
+    // get_describe_params returns parameters of builder (filters, sorts, limit and offset)
+    pub fn get_describe_params(&self) -> Vec<crate::describe::Param<'_>> {
+        fn param(field: &field::Field) -> crate::describe::Param<'_> {
+            let name = field.ident.as_ref().unwrap().to_string();
+            let mut param = crate::describe::Param::new(name, &field.ty);
+            param.description = crate::describe::doc(&field.attrs);
+            param
+        }
+
+        let mut params = vec![];
+
+        // nested filters are described by their own fields
+        for field in self.get_filter_fields() {
+            let mut param = param(field);
+            param.flatten = true;
+            params.push(param);
+        }
+
+        for field in self.get_sort_fields() {
+            let mut param = param(field);
+            if param.description.is_none() {
+                param.description = Some(format!("sort by `{}`", field.sort.as_ref().unwrap()));
+            }
+            params.push(param);
+        }
+
+        for field in [self.get_limit_field(), self.get_offset_field()].iter().flatten() {
+            let mut param = param(field);
+            param.minimum = Some(0);
+            params.push(param);
+        }
+
+        params
+    }
 }
 
 // generate tokens from select
@@ -126,14 +209,13 @@ impl quote::ToTokens for SelectBuilder {
     fn to_tokens(&self, _tokens: &mut TokenStream) {
         let ident = &self.ident;
         let select_field_type = &self.get_select_field().ty;
-        let select_field_ident = &self.get_select_field().ident;
 
         // prepare sort field
         let mut sort_tokens = TokenStream::new();
-        let mut sort_tokens_asserts = TokenStream::new();
         let mut sort_clause = TokenStream::new();
 
-        let mut asserts = TokenStream::new();
+        let mut bounds = crate::generics::Bounds::default();
+        bounds.assert(select_field_type, &self.generics, quote! { ::buildix::Select });
 
         let sorts_len = self.get_sort_fields().len();
 
@@ -143,7 +225,7 @@ impl quote::ToTokens for SelectBuilder {
         // TODO: make better error handling and better implementation possibly
         if let Some(_path) = &self.map {
             map_fn_impl.extend(quote! {
-                let _fun: &dyn Fn(&mut #ident) -> buildix::Result<()> = &#_path;
+                let _fun: &dyn Fn(&mut Self) -> buildix::Result<()> = &#_path;
 
                 // call map function
                 let _ = #_path(self)?;
@@ -155,9 +237,7 @@ impl quote::ToTokens for SelectBuilder {
             let sort_ident = field.ident.as_ref().unwrap();
             let sort_ident_db = &field.sort.as_ref().unwrap();
             let sort_ty = &field.ty;
-            sort_tokens_asserts.extend(quote! {
-                static_assertions::assert_impl_all!(#sort_ty: ::buildix::sort::Sorter);
-            });
+            bounds.assert(sort_ty, &self.generics, quote! { ::buildix::sort::Sorter });
 
             // now do something
             sort_tokens.extend(quote! {
@@ -191,32 +271,228 @@ impl quote::ToTokens for SelectBuilder {
             let limit_field_type = &limit_field.ty;
             let limit_field_ident = &limit_field.ident.as_ref();
             // assert limit type
-            asserts.extend(quote! {
-                static_assertions::assert_impl_all!(#limit_field_type: ::buildix::limit::Limit);
-            });
+            bounds.assert(limit_field_type, &self.generics, quote! { ::buildix::limit::Limit });
 
-            let mut offset_clause = TokenStream::new();
+            let mut offset_tokens = quote! { None };
 
             if let Some(offset_field) = offset_field {
                 let offset_field_type = &offset_field.ty;
                 let offset_field_ident = &offset_field.ident.as_ref();
-                asserts.extend(quote! {
-                    static_assertions::assert_impl_all!(#offset_field_type: ::buildix::offset::Offset);
-                });
+                bounds.assert(offset_field_type, &self.generics, quote! { ::buildix::offset::Offset });
 
-                offset_clause.extend(quote! {
-                    if let Some(clause) = self.#offset_field_ident.get_offset::<DB>() {
-                        parts.push(clause);
+                offset_tokens = quote! {
+                    self.#offset_field_ident.get_offset()
+                };
+            }
+
+            // whether query has ORDER BY (some dialects need it for offset)
+            let ordered_tokens = if sorts_len > 0 {
+                quote! { !sorts.is_empty() }
+            } else {
+                quote! { false }
+            };
+
+            // clause is rendered by dialect
+            limit_offset_clause.extend(quote! {
+                if let Some(limit) = self.#limit_field_ident.get_limit() {
+                    let offset: Option<u64> = #offset_tokens;
+                    let ordered: bool = #ordered_tokens;
+                    parts.push(DB::limit_offset(limit, offset, ordered));
+                }
+            });
+        }
+
+        // base query, possibly with distinct and projection
+        let mut query_tokens = TokenStream::new();
+        let distinct_on = self.get_distinct_on();
+
+        // grouping key chosen at runtime
+        let mut group_tokens = TokenStream::new();
+        let mut group_by_tokens = quote! {
+            <#select_field_type as ::buildix::Select>::get_group::<DB>().map(|g| g.to_string())
+        };
+        let mut group_select = String::new();
+        if let Some(group_field) = self.get_group_field().filter(|f| f.get_group().is_some()) {
+            let group_ident = &group_field.ident;
+            let group_type = &group_field.ty;
+            let group = group_field.get_group().unwrap();
+            group_select = group.select.trim().to_string();
+            let replaced = if group.extend {
+                quote! { None }
+            } else {
+                quote! { Some(#group_select) }
+            };
+
+            let keys: Vec<TokenStream> = group
+                .keys
+                .iter()
+                .map(|k| {
+                    let name = k.name.trim();
+                    let expr = k.expr.trim();
+                    quote! {
+                        ::buildix::select::GroupKey { name: #name, expr: #expr }
                     }
+                })
+                .collect();
+
+            bounds.assert(group_type, &self.generics, quote! { ::buildix::select::Grouping });
+
+            // field selected by grouping key has to be declared by select
+            let static_type = crate::generics::static_type(select_field_type, &self.generics);
+            if let (false, Some(static_type)) = (group_select.is_empty(), static_type) {
+                let message = format!(
+                    "unknown field `{}` in `select` of grouping keys",
+                    group_select
+                );
+                let span = group_field.ident.as_ref().unwrap().span();
+                bounds.asserts.extend(quote_spanned! {span=>
+                    const _: () = assert!(
+                        ::buildix::select::has_field(
+                            <#static_type as ::buildix::Select>::FIELDS,
+                            #group_select,
+                        ),
+                        #message
+                    );
                 });
             }
 
-            limit_offset_clause.extend(quote! {
-                if let Some(clause) = self.#limit_field_ident.get_limit::<DB>() {
-                    parts.push(clause);
+            group_tokens.extend(quote! {
+                const GROUP_KEYS: &[::buildix::select::GroupKey] = &[
+                    #(#keys),*
+                ];
+                let group_key = ::buildix::select::find_group_key(
+                    GROUP_KEYS,
+                    ::buildix::select::Grouping::group_key(&self.#group_ident),
+                )?;
+            });
+            group_by_tokens = quote! {
+                ::buildix::select::group_by(
+                    <#select_field_type as ::buildix::Select>::get_columns(),
+                    <#select_field_type as ::buildix::Select>::GROUP,
+                    #replaced,
+                    group_key,
+                )
+            };
+        }
+        query_tokens.extend(group_tokens);
 
-                    // add offset if available
-                    #offset_clause
+        // requested fields (projection)
+        let mut requested_tokens = quote! {
+            let requested: &[String] = &[];
+        };
+        if let Some(fields_field) = self.get_fields_field() {
+            let fields_ident = &fields_field.ident;
+            let fields_type = &fields_field.ty;
+            bounds.assert(fields_type, &self.generics, quote! { ::buildix::select::Projection });
+            requested_tokens = quote! {
+                let requested: &[String] = ::buildix::select::Projection::requested(&self.#fields_ident);
+            };
+        }
+
+        // fields removed by projection are decoded as default values
+        let mut projection_mask_tokens = TokenStream::new();
+        if self.get_fields_field().is_some() {
+            projection_mask_tokens.extend(quote! {
+                fn projection_mask(&self) -> Vec<&'static str> {
+                    #requested_tokens
+                    ::buildix::select::projection_mask(
+                        <#select_field_type as ::buildix::Select>::get_field_names(),
+                        requested,
+                    )
+                }
+            });
+        }
+
+        // projection (None means all fields)
+        if !group_select.is_empty() {
+            // grouping key replaces expression of select field
+            query_tokens.extend(quote! {
+                #requested_tokens
+                let grouped_fields: Option<Vec<String>> = match group_key {
+                    Some(key) => Some(::buildix::select::group_fields(
+                        <#select_field_type as ::buildix::Select>::get_columns(),
+                        #group_select,
+                        key.expr,
+                    )?),
+                    None => None,
+                };
+                let projection: Option<String> = match &grouped_fields {
+                    Some(fields) => {
+                        let fields: Vec<&str> = fields.iter().map(|f| f.as_str()).collect();
+                        Some(
+                            ::buildix::select::project(
+                                <#select_field_type as ::buildix::Select>::get_field_names(),
+                                &fields,
+                                requested,
+                            )?
+                            .unwrap_or_else(|| fields.join(", ")),
+                        )
+                    }
+                    None => ::buildix::select::project(
+                        <#select_field_type as ::buildix::Select>::get_field_names(),
+                        <#select_field_type as ::buildix::Select>::get_fields::<DB>(),
+                        requested,
+                    )?,
+                };
+            });
+        } else if self.get_fields_field().is_some() {
+            query_tokens.extend(quote! {
+                #requested_tokens
+                let projection: Option<String> = ::buildix::select::project(
+                    <#select_field_type as ::buildix::Select>::get_field_names(),
+                    <#select_field_type as ::buildix::Select>::get_fields::<DB>(),
+                    requested,
+                )?;
+            });
+        } else {
+            query_tokens.extend(quote! {
+                let projection: Option<String> = None;
+            });
+        }
+
+        if !distinct_on.is_empty() {
+            let distinct_on = distinct_on.join(", ");
+            // `postgres` feature is checked at compile time, but features are unified (other
+            // databases may be enabled as well), so database is checked when query is rendered
+            query_tokens.extend(quote! {
+                if !DB::DISTINCT_ON {
+                    return Err(::buildix::Error::Unsupported("DISTINCT ON", DB::NAME));
+                }
+                parts.push(format!(
+                    "SELECT DISTINCT ON ({}) {} FROM {}",
+                    #distinct_on,
+                    projection.as_deref().unwrap_or_else(|| <#select_field_type as ::buildix::Select>::get_fields_str::<DB>()),
+                    <#select_field_type as ::buildix::Select>::get_table::<DB>(),
+                ));
+            });
+        } else {
+            let mut distinct_tokens = quote! {
+                <#select_field_type as ::buildix::Select>::DISTINCT
+            };
+            if let Some(distinct_field) = self.get_distinct_field() {
+                let distinct_ident = &distinct_field.ident;
+                distinct_tokens.extend(quote! {
+                    || self.#distinct_ident
+                });
+            }
+
+            query_tokens.extend(quote! {
+                let distinct: bool = #distinct_tokens;
+                match projection {
+                    None if distinct => {
+                        parts.push(<#select_field_type as ::buildix::Select>::get_query_distinct::<DB>().to_string());
+                    }
+                    None => {
+                        parts.push(<#select_field_type as ::buildix::Select>::get_query::<DB>().to_string());
+                    }
+                    Some(fields) => {
+                        parts.push(format!(
+                            "SELECT {}{} FROM {}",
+                            if distinct { "DISTINCT " } else { "" },
+                            fields,
+                            <#select_field_type as ::buildix::Select>::get_table::<DB>(),
+                        ));
+                    }
                 }
             });
         }
@@ -226,77 +502,174 @@ impl quote::ToTokens for SelectBuilder {
         let filter_fields: Vec<crate::filter::process::Field> = self
             .get_filter_fields()
             .iter()
-            .map(|f| *f)
+            .copied()
             .map(|f| f.into())
             .collect();
 
         // now create filter
         crate::filter::process::process(
             &self.ident,
+            &self.generics,
             filter_fields,
             "AND".to_string(),
             &mut filter_tokens,
         );
 
+        // builders of common table expressions provided by fields
+        let mut with_arms = TokenStream::new();
+        for field in self.get_with_fields() {
+            let ident = field.ident.as_ref().unwrap();
+            let name = field.with.as_ref().unwrap().trim();
+            bounds.assert(&field.ty, &self.generics, quote! { ::buildix::SelectBuilder });
+            with_arms.extend(quote! {
+                #name => Some(::buildix::SelectBuilder::to_raw_sql::<DB>(&mut self.#ident)),
+            });
+        }
+        let with_builders_tokens = if with_arms.is_empty() {
+            quote! {
+                let _ = name;
+                None
+            }
+        } else {
+            quote! {
+                match name {
+                    #with_arms
+                    _ => None,
+                }
+            }
+        };
+
+        // describe parameters
+        if self.describe {
+            crate::describe::write_describe(
+                &self.ident,
+                &self.generics,
+                &self.get_describe_params(),
+                &mut filter_tokens,
+            );
+        }
+
+        // scope is always applied, its column is qualified by main table of select (as soft delete)
+        let mut scope_tokens = TokenStream::new();
+        if let (Some(column), Some(field)) = (&self.scope, self.get_scope_field()) {
+            let ident = field.ident.as_ref().unwrap();
+            bounds.assert(&field.ty, &self.generics, quote! { ::buildix::scope::Scope });
+            scope_tokens.extend(quote! {
+                let scope_column = ::buildix::scope::qualify(
+                    #column,
+                    <#select_field_type as ::buildix::Select>::QUALIFIER,
+                );
+                let (scope_clause, scope_value) = ::buildix::scope::scope_clause(&scope_column, &self.#ident)?;
+                where_clauses.push(scope_clause);
+                values.push(scope_value);
+            });
+        }
+
+        // with_deleted field includes soft deleted rows
+        let with_deleted_tokens = match self.get_with_deleted_field() {
+            Some(field) => {
+                let ident = field.ident.as_ref().unwrap();
+                quote! { self.#ident }
+            }
+            None => quote! { false },
+        };
+
+        let asserts = &bounds.asserts;
+        let (impl_generics, ty_generics, _) = self.generics.split_for_impl();
+        let where_clause = bounds.where_clause(&self.generics);
+
         // generate traits for select
         _tokens.extend(quote! {
-            #[allow(unused_imports)]
-            use buildix::SelectBuilder as _;
-            use buildix::sort::Sort as _;
-            use buildix::offset::Offset as _;
-            use buildix::limit::Limit as _;
-            use buildix::filter::Filter as _;
-            use buildix::prelude::*;
-            use sqlx::database::Database;
-            use static_assertions;
-
-            #asserts
-            #sort_tokens_asserts
-
-            // filter implementation
-            #filter_tokens
-
-            // implement Select
-            impl ::buildix::SelectBuilder for #ident {
-                // get_query returns query string
-                fn to_sql<DB: Database>(&mut self) -> buildix::Result<(String, Vec<()>)> {
-
-                    // first run map function (if available)
-                    #map_fn_impl
-
-                    // prepare query
-                    // first is base query which should be prepared in binary
-                    // TODO: remove vector in favor of String builder.
-                    let mut parts: Vec<String> = vec![self.#select_field_ident.get_query::<DB>().to_string()];
-
-                    // now
-                    let values: Vec<()> = vec![];
-
-                    // filter builder, start with basic filter_info
-                    let fi = buildix::filter::FilterInfo::default();
-                    if let Some(filter_result) = self.process_filter::<DB>(&fi) {
-                        if !filter_result.clause.is_empty() {
-                            parts.push(format!("WHERE {}", filter_result.clause).to_string());
+            const _: () = {
+                #[allow(unused_imports)]
+                use buildix::SelectBuilder as _;
+                use buildix::sort::Sort as _;
+                use buildix::offset::Offset as _;
+                use buildix::limit::Limit as _;
+                use buildix::filter::Filter as _;
+                use buildix::prelude::*;
+                use ::buildix::dialect::Dialect;
+                use static_assertions;
+
+                #asserts
+
+                // filter implementation
+                #filter_tokens
+
+                // implement Select
+                impl #impl_generics ::buildix::select::Rows<<#select_field_type as ::buildix::Select>::Row> for #ident #ty_generics #where_clause {}
+
+                impl #impl_generics ::buildix::SelectBuilder for #ident #ty_generics #where_clause {
+                    #projection_mask_tokens
+
+                    // to_raw_sql returns query string (with `?` placeholders)
+                    fn to_raw_sql<DB: Dialect>(&mut self) -> buildix::Result<(String, Vec<::buildix::value::Value>)> {
+
+                        // first run map function (if available)
+                        #map_fn_impl
+
+                        // prepare query
+                        // TODO: remove vector in favor of String builder.
+                        let mut parts: Vec<String> = vec![];
+                        let mut values: Vec<::buildix::value::Value> = vec![];
+
+                        // common table expressions go first, so their values are first as well
+                        let with = {
+                            let mut builders = |name: &str| -> Option<::buildix::Result<(String, Vec<::buildix::value::Value>)>> {
+                                #with_builders_tokens
+                            };
+                            <#select_field_type as ::buildix::Select>::get_with::<DB>(&mut builders)?
+                        };
+                        if let Some((with_clause, with_values)) = with {
+                            parts.push(with_clause);
+                            values.extend(with_values);
                         }
-                    }
 
-                    // GROUP BY
-                    if let Some(group_by) = self.#select_field_ident.get_group::<DB>() {
-                        parts.push(group_by.to_owned());
-                    }
+                        // base query which should be prepared in binary
+                        #query_tokens
+
+                        let mut where_clauses: Vec<String> = vec![];
 
-                    #sort_clause
+                        // scope goes first (after values of common table expressions)
+                        #scope_tokens
 
-                    #limit_offset_clause
+                        // soft deleted rows are excluded (unless they are requested)
+                        let with_deleted: bool = #with_deleted_tokens;
+                        if let (Some(column), false) = (<#select_field_type as ::buildix::Select>::SOFT_DELETE, with_deleted) {
+                            where_clauses.push(::buildix::delete::soft_delete_check::<DB>(
+                                column,
+                                <#select_field_type as ::buildix::Select>::SOFT_DELETE_FLAG,
+                            ));
+                        }
 
-                    let query = parts.join(" ");
+                        // filter builder, start with basic filter_info
+                        let fi = buildix::filter::FilterInfo::default();
+                        if let Some(filter_result) = self.process_filter::<DB>(&fi) {
+                            if !filter_result.clause.is_empty() {
+                                where_clauses.push(filter_result.clause);
+                                values.extend(filter_result.values);
+                            }
+                        }
 
-                    Ok((query, values))
-                }
-            }
+                        if !where_clauses.is_empty() {
+                            parts.push(format!("WHERE {}", ::buildix::filter::and(&where_clauses)));
+                        }
+
+                        // GROUP BY
+                        if let Some(group_by) = #group_by_tokens {
+                            parts.push(group_by);
+                        }
+
+                        #sort_clause
+
+                        #limit_offset_clause
 
-            // assert that everything is fine
-            static_assertions::assert_impl_all!(#select_field_type: ::buildix::Select);
+                        let query = parts.join(" ");
+
+                        Ok((query, values))
+                    }
+                }
+            };
         })
     }
 }
@@ -320,6 +693,13 @@ pub fn validate(s: SelectBuilder) -> SelectBuilder {
         if let Err(ref err) = f.validate() {
             abort!(f_ident, err);
         };
+
+        // grouping keys need to be valid expressions
+        for key in f.get_group().iter().flat_map(|g| g.keys.iter()) {
+            if let Err(err) = crate::sql::check_expr(key.expr.trim()) {
+                abort!(key.expr.span(), crate::Error::InvalidSql(err));
+            }
+        }
     });
 
     // check if we have query
@@ -330,7 +710,7 @@ pub fn validate(s: SelectBuilder) -> SelectBuilder {
     // check for duplicates
     s.validate_single(
         |f| f.select,
-        crate::Error::MultipleFields("#[buildix(query)]".to_string()),
+        crate::Error::MultipleFields("#[buildix(select)]".to_string()),
     );
     s.validate_single(
         |f| f.offset,
@@ -342,9 +722,141 @@ pub fn validate(s: SelectBuilder) -> SelectBuilder {
     );
     // check for duplicate group
     s.validate_single(
-        |f| f.group,
+        |f| f.group.is_some(),
         crate::Error::MultipleFields("#[buildix(group)]".to_string()),
     );
+    // common table expression has single builder
+    let mut with_names: Vec<&str> = vec![];
+    for field in s.get_with_fields() {
+        let name = field.with.as_ref().unwrap();
+        if name.trim().is_empty() || with_names.contains(&name.trim()) {
+            abort!(
+                field.ident,
+                crate::Error::InvalidWith(format!("invalid or duplicate name `{}`", name))
+            );
+        }
+        with_names.push(name.trim());
+    }
+
+    s.validate_single(
+        |f| f.distinct,
+        crate::Error::MultipleFields("#[buildix(distinct)]".to_string()),
+    );
+    s.validate_single(
+        |f| f.fields,
+        crate::Error::MultipleFields("#[buildix(fields)]".to_string()),
+    );
+    s.validate_single(
+        |f| f.with_deleted,
+        crate::Error::MultipleFields("#[buildix(with_deleted)]".to_string()),
+    );
+    s.validate_single(
+        |f| f.scope,
+        crate::Error::MultipleFields("#[buildix(scope)]".to_string()),
+    );
+    let mut s = s;
+    let scope_field = s.get_scope_field().and_then(|f| f.ident.clone());
+    validate_scope(&s.ident, &mut s.scope, scope_field.as_ref());
+
+    validate_distinct_on(&s);
 
     s
 }
+
+// validate_scope checks that scope column is provided along with its `#[buildix(scope)]` field
+pub fn validate_scope(ident: &syn::Ident, scope: &mut Option<String>, field: Option<&syn::Ident>) {
+    if let Some(column) = scope {
+        *column = column.trim().to_string();
+        if column.is_empty() {
+            abort!(ident, crate::Error::InvalidScope("empty column".to_string()));
+        }
+    }
+
+    match (scope.as_ref(), field) {
+        (Some(column), None) => abort!(
+            ident,
+            crate::Error::InvalidScope(format!(
+                "please provide `#[buildix(scope)]` field with value of `{}`",
+                column
+            ))
+        ),
+        (None, Some(field)) => abort!(
+            field,
+            crate::Error::InvalidScope(
+                "please provide scope column by `#[buildix(scope = \"...\")]`".to_string()
+            )
+        ),
+        _ => {}
+    }
+}
+
+// validate_distinct_on checks that `distinct_on` is used with postgres and that ORDER BY always
+// starts with distinct on expressions (which postgres requires).
+fn validate_distinct_on(s: &SelectBuilder) {
+    let distinct_on = s.get_distinct_on();
+    if distinct_on.is_empty() {
+        return;
+    }
+
+    if !cfg!(feature = "postgres") {
+        abort!(
+            s.ident,
+            crate::Error::InvalidDistinctOn("only supported with `postgres` feature".to_string())
+        );
+    }
+
+    if let Some(distinct_field) = s.get_distinct_field() {
+        abort!(
+            distinct_field.ident,
+            crate::Error::InvalidDistinctOn(
+                "cannot be used along with #[buildix(distinct)] field".to_string()
+            )
+        );
+    }
+
+    let sorts = s.get_sort_fields();
+    for (i, expr) in distinct_on.iter().enumerate() {
+        let sort = match sorts.get(i) {
+            Some(sort) => sort,
+            None => abort!(
+                s.ident,
+                crate::Error::InvalidDistinctOn(format!(
+                    "ORDER BY must start with `{}`, please provide #[buildix(sort = \"{}\")] field",
+                    expr, expr
+                ))
+            ),
+        };
+
+        if sort.sort.as_ref().unwrap().trim() != expr {
+            abort!(
+                sort.ident,
+                crate::Error::InvalidDistinctOn(format!(
+                    "ORDER BY must start with `{}`, found `{}`",
+                    expr,
+                    sort.sort.as_ref().unwrap()
+                ))
+            );
+        }
+
+        // optional sort would not be rendered
+        if is_option(&sort.ty) {
+            abort!(
+                sort.ident,
+                crate::Error::InvalidDistinctOn(format!(
+                    "sort for `{}` must always be set, please use `buildix::sort::Sort`",
+                    expr
+                ))
+            );
+        }
+    }
+}
+
+// is_option returns whether type is Option
+pub(crate) fn is_option(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last() {
+            return segment.ident == "Option";
+        }
+    }
+    false
+}
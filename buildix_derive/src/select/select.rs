@@ -2,6 +2,7 @@
 #![allow(unused_imports)]
 
 use crate::error::Error;
+use darling::util::SpannedValue;
 use darling::{self, ast, util, FromDeriveInput, FromField, FromMeta, FromVariant};
 use proc_macro2::TokenStream;
 use proc_macro_error::*;
@@ -18,6 +19,8 @@ pub struct Select {
     // indent
     ident: syn::Ident,
 
+    generics: syn::Generics,
+
     // data
     data: ast::Data<util::Ignored, Field>,
 
@@ -28,7 +31,47 @@ pub struct Select {
     froms: Vec<FromAttribute>,
 
     #[darling(default, multiple)]
-    group: Vec<String>,
+    group: Vec<SpannedValue<String>>,
+
+    #[darling(default, multiple, rename = "with")]
+    withs: Vec<WithAttribute>,
+
+    #[darling(default)]
+    distinct: bool,
+
+    // table name (when no `from(table(...))` is provided)
+    #[darling(default)]
+    table: String,
+
+    // rename rule to infer table name from struct name (default `snake_case`)
+    #[darling(default)]
+    rename_all: String,
+
+    // DDL file to validate tables and columns against
+    #[darling(default)]
+    schema: Option<SpannedValue<String>>,
+
+    // column marking soft deleted rows (excluded by builders)
+    #[darling(default)]
+    soft_delete: Option<String>,
+
+    // soft delete column is boolean flag instead of timestamp
+    #[darling(default)]
+    soft_delete_flag: bool,
+}
+
+// WithAttribute is common table expression, where query is provided by select builder
+#[derive(Debug, FromMeta)]
+pub struct WithAttribute {
+    name: String,
+
+    builder: syn::Path,
+
+    #[darling(default)]
+    columns: String,
+
+    #[darling(default)]
+    recursive: bool,
 }
 
 #[derive(Debug, FromField)]
@@ -45,13 +88,86 @@ pub struct Field {
     pub ty: syn::Type,
 
     #[darling(default)]
-    pub expr: String,
+    pub expr: SpannedValue<String>,
 
     #[darling(default)]
     pub table: String,
 
     #[darling(default)]
     pub column: String,
+
+    // decode default value when column is not selected (Option fields are always defaulted)
+    #[darling(default)]
+    pub default: bool,
+
+    // inline columns of nested select, their names are prefixed (`{field}_` by default)
+    #[darling(default)]
+    pub flatten: bool,
+
+    #[darling(default)]
+    pub prefix: Option<String>,
+
+    // aggregate function over `column` (count, sum, avg, min, max)
+    #[darling(default)]
+    pub aggregate: String,
+}
+
+// supported aggregate functions
+const AGGREGATES: &[&str] = &["count", "sum", "avg", "min", "max"];
+
+impl Field {
+    // get_prefix returns prefix of flattened columns
+    pub fn get_prefix(&self) -> String {
+        match &self.prefix {
+            Some(prefix) => prefix.clone(),
+            None => format!("{}_", self.ident.as_ref().unwrap()),
+        }
+    }
+
+    // group_expr returns expression of field as used in GROUP BY
+    pub fn group_expr(&self) -> String {
+        if !self.expr.is_empty() {
+            return self.expr.to_string();
+        }
+        if !self.table.is_empty() {
+            return format!("{}.{}", self.table, self.column);
+        }
+        self.column.clone()
+    }
+
+    // render returns field as used in select (same as `buildix::select::Column::render`)
+    pub fn render(&self) -> String {
+        let name = self.ident.as_ref().unwrap().to_string();
+        if !self.expr.is_empty() {
+            return format!("{} AS {}", self.expr.as_str(), name);
+        }
+
+        let mut rendered = self.column.clone();
+        if !self.table.is_empty() {
+            rendered = format!("{}.{}", self.table, rendered);
+        }
+        if self.column != name {
+            rendered = format!("{} AS {}", rendered, name);
+        }
+        rendered
+    }
+
+    // write_column writes `buildix::select::Column` of field
+    pub fn write_column(&self, tokens: &mut TokenStream) {
+        let name = self.ident.as_ref().unwrap().to_string();
+        let table = &self.table;
+        let column = &self.column;
+        let expr = self.expr.as_str();
+
+        tokens.extend(quote! {
+            ::buildix::select::Column {
+                name: #name,
+                table: #table,
+                column: #column,
+                expr: #expr,
+            }
+        });
+    }
 }
 
 // map_field is called before init struct, we can do our checks and prepare `column` which is
@@ -60,34 +176,83 @@ fn map_field(f: Field) -> Field {
     let mut f = f;
 
     // first trim off whitespace
-    f.expr = f.expr.trim().to_string();
+    *f.expr = f.expr.trim().to_string();
     f.table = f.table.trim().to_string();
     f.column = f.column.trim().to_string();
-
-    let original_ident = f.ident.as_ref().unwrap().to_string();
+    f.prefix = f.prefix.map(|p| p.trim().to_string());
+    f.aggregate = f.aggregate.trim().to_lowercase();
 
     // validate
     if !f.expr.is_empty() && !f.table.is_empty() {
         abort!(f.ident.as_ref().unwrap(), Error::InvalidSelectField)
     }
 
-    let mut what = "".to_string();
-
-    if f.expr.is_empty() {
-        if !f.column.is_empty() {
-            f.expr = f.column.clone();
-        } else {
-            f.expr = original_ident.clone()
+    if !f.aggregate.is_empty() {
+        if !AGGREGATES.contains(&f.aggregate.as_str()) {
+            abort!(
+                f.ident.as_ref().unwrap(),
+                Error::InvalidAggregate(format!(
+                    "unknown function `{}`, please provide one of: {}",
+                    f.aggregate,
+                    AGGREGATES.join(", ")
+                ))
+            )
+        }
+        if !f.expr.is_empty() || f.flatten {
+            abort!(
+                f.ident.as_ref().unwrap(),
+                Error::InvalidAggregate(
+                    "`aggregate` cannot be used along with `expr` or `flatten`".to_string()
+                )
+            )
         }
-        what = f.expr.clone();
 
+        // aggregate is rendered as expression, `count` without column counts all rows
+        if f.column.is_empty() {
+            f.column = match f.aggregate.as_str() {
+                "count" => "*".to_string(),
+                _ => f.ident.as_ref().unwrap().to_string(),
+            };
+        }
+        let mut column = f.column.clone();
         if !f.table.is_empty() {
-            f.expr = format!("{}.{}", f.table, f.expr);
+            column = format!("{}.{}", f.table, column);
         }
+        *f.expr = format!("{}({})", f.aggregate.to_uppercase(), column);
+        if let Err(err) = crate::sql::check_expr(&f.expr) {
+            abort!(f.ident.as_ref().unwrap(), Error::InvalidSql(err))
+        }
+        return f;
     }
 
-    if what != original_ident {
-        f.expr = format!("{} AS {}", f.expr, original_ident);
+    if f.flatten {
+        // table of flattened struct replaces table of its columns
+        if !f.expr.is_empty() || !f.column.is_empty() || f.default {
+            abort!(
+                f.ident.as_ref().unwrap(),
+                Error::InvalidFlatten(
+                    "only `prefix` and `table` can be used along with `flatten`".to_string()
+                )
+            )
+        }
+        return f;
+    }
+
+    if f.prefix.is_some() {
+        abort!(
+            f.ident.as_ref().unwrap(),
+            Error::InvalidFlatten("`prefix` can only be used along with `flatten`".to_string())
+        )
+    }
+
+    if f.expr.is_empty() && f.column.is_empty() {
+        f.column = f.ident.as_ref().unwrap().to_string();
+    }
+
+    if !f.expr.is_empty() {
+        if let Err(err) = crate::sql::check_expr(&f.expr) {
+            abort!(f.expr.span(), Error::InvalidSql(err))
+        }
     }
 
     f
@@ -112,7 +277,7 @@ pub enum FromAttribute {
         alias: String,
 
         #[darling(default, rename = "on")]
-        join_on: String,
+        join_on: SpannedValue<String>,
 
         #[darling(default)]
         join_type: String,
@@ -131,11 +296,48 @@ fn map_from_attribute(t: FromAttribute) -> FromAttribute {
 }
 
 impl Select {
+    // get_soft_delete returns soft delete column, qualified by alias (or table) of main table when
+    // select has alias or joins (otherwise the column would be ambiguous)
+    pub fn get_soft_delete(&self) -> Option<String> {
+        let column = self.soft_delete.as_deref().map(str::trim).unwrap_or_default();
+        if column.is_empty() {
+            return None;
+        }
+        if column.contains('.') {
+            return Some(column.to_string());
+        }
+
+        match self.get_qualifier() {
+            Some(qualifier) => Some(format!("{}.{}", qualifier, column)),
+            None => Some(column.to_string()),
+        }
+    }
+
+    // get_qualifier returns alias of main table, or its name when select has joins (columns of
+    // main table are qualified by it), None when columns are unambiguous
+    pub fn get_qualifier(&self) -> Option<String> {
+        let has_join = self
+            .froms
+            .iter()
+            .any(|f| matches!(f, FromAttribute::Join { .. }));
+        let table = self.froms.iter().find_map(|f| match f {
+            FromAttribute::Table { name, alias } => Some((name.trim(), alias.trim())),
+            _ => None,
+        });
+
+        match table {
+            Some((_, alias)) if !alias.is_empty() => Some(alias.to_string()),
+            Some((name, _)) if has_join => Some(name.to_string()),
+            _ => None,
+        }
+    }
+
     pub fn get_group_by(&self) -> Option<String> {
         if self.group.is_empty() {
             None
         } else {
-            Some(format!("GROUP BY {}", self.group.join(", ")))
+            let group: Vec<&str> = self.group.iter().map(|g| g.as_str()).collect();
+            Some(format!("GROUP BY {}", group.join(", ")))
         }
     }
 
@@ -173,7 +375,7 @@ impl Select {
                         join_type = format!("{} join", join_type).to_uppercase();
                     }
 
-                    let mut join_on = join_on.clone();
+                    let mut join_on = join_on.trim().to_string();
                     if !join_on.is_empty() {
                         join_on = format!("({})", join_on);
                     }
@@ -190,8 +392,383 @@ impl Select {
 
 impl Select {
     // get_fields returns all fields
-    pub fn get_fields(&self) -> Vec<String> {
-        vec![]
+    pub fn get_fields(&self) -> Vec<&Field> {
+        self.data.as_ref().take_struct().unwrap().fields
+    }
+
+    // has_flatten returns whether any field is flattened (columns are then rendered at runtime)
+    pub fn has_flatten(&self) -> bool {
+        self.get_fields().iter().any(|f| f.flatten)
+    }
+
+    // write_with writes `get_with` body which renders all common table expressions
+    pub fn write_with(&self, tokens: &mut TokenStream) {
+        if self.withs.is_empty() {
+            tokens.extend(quote! {
+                let _ = builders;
+                Ok(None)
+            });
+            return;
+        }
+
+        let recursive = if self.withs.iter().any(|w| w.recursive) {
+            "RECURSIVE "
+        } else {
+            ""
+        };
+        let withs_len = self.withs.len();
+        let mut with_tokens = TokenStream::new();
+
+        for with in &self.withs {
+            let builder = &with.builder;
+            let key = &with.name;
+            let mut name = with.name.clone();
+            if !with.columns.is_empty() {
+                name = format!("{}({})", name, with.columns);
+            }
+
+            // builder provided by select builder, default one otherwise
+            with_tokens.extend(quote! {
+                let (query, builder_values) = match builders(#key) {
+                    Some(result) => result?,
+                    None => {
+                        let mut builder = <#builder as ::std::default::Default>::default();
+                        ::buildix::SelectBuilder::to_raw_sql::<DB>(&mut builder)?
+                    }
+                };
+                ctes.push(format!("{} AS ({})", #name, query));
+                values.extend(builder_values);
+            });
+        }
+
+        tokens.extend(quote! {
+            let mut ctes: Vec<String> = Vec::with_capacity(#withs_len);
+            let mut values: Vec<::buildix::value::Value> = vec![];
+
+            #with_tokens
+
+            Ok(Some((format!("WITH {}{}", #recursive, ctes.join(", ")), values)))
+        });
+    }
+}
+
+impl Select {
+    // write_columns writes methods returning columns and rendered query parts. These are static
+    // strings, but selects with flattened fields need columns of nested selects, so they are
+    // rendered once at runtime.
+    pub fn write_columns(&self, tokens: &mut TokenStream, table: &str) {
+        let fields = self.get_fields();
+        let names: Vec<String> = fields
+            .iter()
+            .map(|f| f.ident.as_ref().unwrap().to_string())
+            .collect();
+
+        if !self.has_flatten() {
+            let mut columns = vec![];
+            for field in &fields {
+                let mut column = TokenStream::new();
+                field.write_column(&mut column);
+                columns.push(column);
+            }
+
+            let rendered: Vec<String> = fields.iter().map(|f| f.render()).collect();
+            let all_fields = rendered.join(", ");
+            let query_distinct = format!("SELECT DISTINCT {} FROM {}", all_fields, table);
+            let query = if self.distinct {
+                query_distinct.clone()
+            } else {
+                format!("SELECT {} FROM {}", all_fields, table)
+            };
+
+            tokens.extend(quote! {
+                #[inline]
+                fn get_columns() -> &'static [::buildix::select::Column] {
+                    &[
+                        #(#columns),*
+                    ]
+                }
+                #[inline]
+                fn get_field_names() -> &'static [&'static str] {
+                    &[
+                        #(#names),*
+                    ]
+                }
+                #[inline]
+                fn get_fields<DB: Dialect>() -> &'static [&'static str] {
+                    &[
+                        #(#rendered),*
+                    ]
+                }
+                #[inline]
+                fn get_fields_str<DB: Dialect>() -> &'static str {
+                    #all_fields
+                }
+                #[inline]
+                fn get_table<DB: Dialect>() -> &'static str {
+                    #table
+                }
+                #[inline]
+                fn get_query<DB: Dialect>() -> &'static str {
+                    #query
+                }
+                #[inline]
+                fn get_query_distinct<DB: Dialect>() -> &'static str {
+                    #query_distinct
+                }
+            });
+            return;
+        }
+
+        tokens.extend(quote! {
+            #[inline]
+            fn get_columns() -> &'static [::buildix::select::Column] {
+                Self::__buildix_rendered().columns
+            }
+            #[inline]
+            fn get_field_names() -> &'static [&'static str] {
+                Self::__buildix_rendered().names
+            }
+            #[inline]
+            fn get_fields<DB: Dialect>() -> &'static [&'static str] {
+                Self::__buildix_rendered().fields
+            }
+            #[inline]
+            fn get_fields_str<DB: Dialect>() -> &'static str {
+                Self::__buildix_rendered().fields_str
+            }
+            #[inline]
+            fn get_table<DB: Dialect>() -> &'static str {
+                #table
+            }
+            #[inline]
+            fn get_query<DB: Dialect>() -> &'static str {
+                Self::__buildix_rendered().query
+            }
+            #[inline]
+            fn get_query_distinct<DB: Dialect>() -> &'static str {
+                Self::__buildix_rendered().query_distinct
+            }
+        });
+    }
+
+    // write_rendered writes `rendered` function for selects with flattened fields
+    pub fn write_rendered(
+        &self,
+        tokens: &mut TokenStream,
+        table: &str,
+        bounds: &crate::generics::Bounds,
+    ) {
+        if !self.has_flatten() {
+            return;
+        }
+
+        let distinct = self.distinct;
+        let mut push_tokens = TokenStream::new();
+        for field in self.get_fields() {
+            if field.flatten {
+                let ty = &field.ty;
+                let prefix = field.get_prefix();
+                let field_table = &field.table;
+                push_tokens.extend(quote! {
+                    columns.extend(
+                        <#ty as ::buildix::Select>::get_columns()
+                            .iter()
+                            .map(|c| c.flatten(#prefix, #field_table)),
+                    );
+                });
+            } else {
+                let mut column = TokenStream::new();
+                field.write_column(&mut column);
+                push_tokens.extend(quote! {
+                    columns.push(#column);
+                });
+            }
+        }
+
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, _) = self.generics.split_for_impl();
+
+        // generic selects are rendered by type
+        if self.generics.params.is_empty() {
+            tokens.extend(quote! {
+                impl #ident {
+                    fn __buildix_rendered() -> &'static ::buildix::select::Rendered {
+                        static RENDERED: ::std::sync::OnceLock<::buildix::select::Rendered> =
+                            ::std::sync::OnceLock::new();
+
+                        RENDERED.get_or_init(|| {
+                            let mut columns: Vec<::buildix::select::Column> = vec![];
+                            #push_tokens
+                            ::buildix::select::Rendered::new(columns, #table, #distinct)
+                        })
+                    }
+                }
+            });
+        } else {
+            let predicates = bounds.inline(&self.generics);
+            tokens.extend(quote! {
+                impl #impl_generics #ident #ty_generics
+                where
+                    #predicates
+                {
+                    fn __buildix_rendered() -> &'static ::buildix::select::Rendered {
+                        ::buildix::select::rendered_by_type::<Self>(|| {
+                            let mut columns: Vec<::buildix::select::Column> = vec![];
+                            #push_tokens
+                            ::buildix::select::Rendered::new(columns, #table, #distinct)
+                        })
+                    }
+                }
+            });
+        }
+    }
+
+    // write_fields writes `Fields` implementation, so filters can be evaluated against rows in
+    // memory. Fields of types not convertible to value have no value, flattened fields are
+    // looked up by their prefix.
+    pub fn write_fields(&self, tokens: &mut TokenStream, struct_bounds: &crate::generics::Bounds) {
+        let ident = &self.ident;
+        let mut bounds = TokenStream::new();
+        let mut match_tokens = TokenStream::new();
+        let mut flatten_tokens = TokenStream::new();
+
+        for field in self.get_fields() {
+            let field_ident = field.ident.as_ref().unwrap();
+            let field_name = field_ident.to_string();
+            let ty = &field.ty;
+
+            if field.flatten {
+                let prefix = field.get_prefix();
+                bounds.extend(quote! {
+                    #ty: ::buildix::filter::Fields,
+                });
+                flatten_tokens.extend(quote! {
+                    if let Some(value) = name
+                        .strip_prefix(#prefix)
+                        .and_then(|name| ::buildix::filter::Fields::field(&self.#field_ident, name))
+                    {
+                        return Some(value);
+                    }
+                });
+                continue;
+            }
+
+            match_tokens.extend(quote! {
+                #field_name => (&::buildix::value::ValueOf(&self.#field_ident)).value_of(),
+            });
+        }
+
+        let (impl_generics, ty_generics, _) = self.generics.split_for_impl();
+        let predicates = struct_bounds.inline(&self.generics);
+
+        tokens.extend(quote! {
+            impl #impl_generics ::buildix::filter::Fields for #ident #ty_generics
+            where
+                #predicates
+                #bounds
+            {
+                fn field(&self, name: &str) -> Option<::buildix::value::Value> {
+                    #[allow(unused_imports)]
+                    use ::buildix::value::{ToNoValue as _, ToSomeValue as _};
+
+                    match name {
+                        #match_tokens
+                        _ => {
+                            #flatten_tokens
+                            None
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    // write_from_row writes `sqlx::FromRow` implementation. Columns are decoded by field names
+    // (which are also aliases in query).
+    pub fn write_from_row(&self, tokens: &mut TokenStream, struct_bounds: &crate::generics::Bounds) {
+        let ident = &self.ident;
+        let mut bounds = TokenStream::new();
+        let mut decode_tokens = TokenStream::new();
+        let mut field_idents = vec![];
+
+        for field in self.get_fields() {
+            let field_ident = field.ident.as_ref().unwrap();
+            let field_name = field_ident.to_string();
+            let ty = &field.ty;
+
+            // flattened struct is decoded from its prefixed columns
+            if field.flatten {
+                let prefix = field.get_prefix();
+                bounds.extend(quote! {
+                    #ty: ::buildix::select::FromRowPrefixed<'r, R>,
+                });
+                decode_tokens.extend(quote! {
+                    let #field_ident: #ty = ::buildix::select::FromRowPrefixed::from_row_prefixed(
+                        row,
+                        &::buildix::select::column_name(prefix, #prefix),
+                    )?;
+                });
+                field_idents.push(field_ident);
+                continue;
+            }
+
+            bounds.extend(quote! {
+                #ty: ::buildix::sqlx::decode::Decode<'r, R::Database> + ::buildix::sqlx::types::Type<R::Database>,
+            });
+
+            if field.default || crate::select::is_option(ty) {
+                bounds.extend(quote! {
+                    #ty: ::std::default::Default,
+                });
+                decode_tokens.extend(quote! {
+                    let #field_ident: #ty = ::buildix::select::get_column_or_default(row, prefix, #field_name)?;
+                });
+            } else {
+                decode_tokens.extend(quote! {
+                    let #field_ident: #ty = ::buildix::select::get_column(row, prefix, #field_name)?;
+                });
+            }
+
+            field_idents.push(field_ident);
+        }
+
+        let generics = crate::generics::prepend(
+            &self.generics,
+            vec![syn::parse_quote!('r), syn::parse_quote!(R)],
+        );
+        let (impl_generics, _, _) = generics.split_for_impl();
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let predicates = struct_bounds.inline(&self.generics);
+
+        tokens.extend(quote! {
+            impl #impl_generics ::buildix::select::FromRowPrefixed<'r, R> for #ident #ty_generics
+            where
+                R: ::buildix::sqlx::Row,
+                for<'a> &'a str: ::buildix::sqlx::ColumnIndex<R>,
+                #predicates
+                #bounds
+            {
+                fn from_row_prefixed(row: &'r R, prefix: &str) -> ::std::result::Result<Self, ::buildix::sqlx::Error> {
+                    #decode_tokens
+
+                    Ok(Self {
+                        #(#field_idents),*
+                    })
+                }
+            }
+
+            impl #impl_generics ::buildix::sqlx::FromRow<'r, R> for #ident #ty_generics
+            where
+                R: ::buildix::sqlx::Row,
+                #predicates
+                Self: ::buildix::select::FromRowPrefixed<'r, R>,
+            {
+                #[inline]
+                fn from_row(row: &'r R) -> ::std::result::Result<Self, ::buildix::sqlx::Error> {
+                    <Self as ::buildix::select::FromRowPrefixed<'r, R>>::from_row_prefixed(row, "")
+                }
+            }
+        });
     }
 }
 
@@ -199,21 +776,21 @@ impl Select {
 impl quote::ToTokens for Select {
     fn to_tokens(&self, _tokens: &mut TokenStream) {
         let ident = &self.ident;
+        let distinct = self.distinct;
+
+        let table = self.get_from_tables(&self.ident);
+        let mut columns_tokens = TokenStream::new();
+        self.write_columns(&mut columns_tokens, &table);
 
-        // list over all
-        let fields: Vec<String> = self
-            .data
-            .as_ref()
-            .take_struct()
-            .unwrap()
-            .fields
+
+        // declared fields and static group entries, used by grouping keys of builders
+        let own_fields: Vec<String> = self
+            .get_fields()
             .iter()
-            .map(|f| f.expr.clone())
+            .filter(|f| !f.flatten)
+            .map(|f| f.ident.as_ref().unwrap().to_string())
             .collect();
-
-        let all_fields = fields.join(", ");
-        let table = self.get_from_tables(&self.ident);
-        let query = format!("SELECT {} FROM {}", all_fields, table);
+        let group_entries: Vec<&str> = self.group.iter().map(|g| g.as_str()).collect();
 
         let mut group_tokens = TokenStream::new();
 
@@ -227,54 +804,411 @@ impl quote::ToTokens for Select {
             });
         }
 
+        let mut with_tokens = TokenStream::new();
+        self.write_with(&mut with_tokens);
+
+        let soft_delete = match self.get_soft_delete() {
+            Some(column) => quote! { Some(#column) },
+            None => quote! { None },
+        };
+        let soft_delete_flag = self.soft_delete_flag;
+        let qualifier = match self.get_qualifier() {
+            Some(qualifier) => quote! { Some(#qualifier) },
+            None => quote! { None },
+        };
+
+
+        // rebuild when schema changes
+        let mut schema_tokens = TokenStream::new();
+        crate::schema::Schema::write_env_dependency(&mut schema_tokens);
+        if let Some(schema) = crate::schema::Schema::load(&self.schema) {
+            schema.write_dependency(&mut schema_tokens);
+        }
+
+        // assert that flattened fields are selects and common table expressions are provided by
+        // select builders
+        let mut bounds = crate::generics::Bounds::default();
+        for field in self.get_fields().iter().filter(|f| f.flatten) {
+            bounds.assert(&field.ty, &self.generics, quote! { ::buildix::Select });
+        }
+        let mut asserts = bounds.asserts.clone();
+        for with in &self.withs {
+            let builder = &with.builder;
+            asserts.extend(quote! {
+                static_assertions::assert_impl_all!(#builder: ::buildix::SelectBuilder, ::std::default::Default);
+            });
+        }
+
+        // generic selects with flattened fields are rendered by type id
+        if self.has_flatten() && !self.generics.params.is_empty() {
+            bounds.predicates.push(quote! { Self: 'static });
+        }
+
+        let mut rendered_tokens = TokenStream::new();
+        self.write_rendered(&mut rendered_tokens, &table, &bounds);
+
+        let mut fields_tokens = TokenStream::new();
+        self.write_fields(&mut fields_tokens, &bounds);
+
+        // rows are decoded by sqlx only when enabled
+        let mut from_row_tokens = TokenStream::new();
+        if cfg!(feature = "sqlx") {
+            self.write_from_row(&mut from_row_tokens, &bounds);
+        }
+
+        let (impl_generics, ty_generics, _) = self.generics.split_for_impl();
+        let where_clause = bounds.where_clause(&self.generics);
+
         _tokens.extend(quote! {
-            #[allow(unused_imports)]
-            use buildix::Select as _;
-            use sqlx::postgres::Postgres as _;
-            use sqlx::database::Database as _;
+            const _: () = {
+                use ::buildix::dialect::Dialect;
 
-            // implement query first
-            impl ::buildix::Select for #ident {
-                #[inline]
-                fn get_fields_str<DB: Database>(&self) -> &'static str {
-                    #all_fields
-                }
-                #[inline]
-                fn get_table<DB: Database>(&self) -> &'static str {
-                    #table
-                }
-                #[inline]
-                fn get_fields<DB: Database>(&self) -> &'static [&'static str] {
-                    &[
-                        #(#fields),*
-                    ]
-                }
-                #[inline]
-                fn get_query<DB: Database>(&self) -> &'static str {
-                    #query
-                }
-                #[inline]
-                fn get_group<DB: Database>(&mut self) -> Option<&'static str> {
-                    #group_tokens
+                #asserts
+                #schema_tokens
+
+                #rendered_tokens
+
+                // implement query first
+                impl #impl_generics ::buildix::Select for #ident #ty_generics #where_clause {
+                    type Row = Self;
+
+                    const DISTINCT: bool = #distinct;
+                    const SOFT_DELETE: Option<&'static str> = #soft_delete;
+                    const SOFT_DELETE_FLAG: bool = #soft_delete_flag;
+                    const QUALIFIER: Option<&'static str> = #qualifier;
+                    const FIELDS: &'static [&'static str] = &[#(#own_fields),*];
+                    const GROUP: &'static [&'static str] = &[#(#group_entries),*];
+
+                    #columns_tokens
+
+                    #[inline]
+                    fn get_group<DB: Dialect>() -> Option<&'static str> {
+                        #group_tokens
+                    }
+                    fn get_with<DB: Dialect>(builders: &mut ::buildix::select::WithBuilders<'_>) -> ::buildix::Result<Option<(String, Vec<::buildix::value::Value>)>> {
+                        #with_tokens
+                    }
                 }
-            }
+
+                // in memory filtering
+                #fields_tokens
+
+                // decode rows
+                #from_row_tokens
+            };
         })
     }
 }
 
 // validate query
 fn validate(q: Select) -> Select {
-    // let mut q = q;
-    // if q.tables.is_empty() {
-    //     q.tables.push(Table::default())
-    // }
-    //
-    // for mut t in &mut q.tables {
-    //     // verify if name is provided
-    //     if t.name.is_empty() && t.join.is_empty() {
-    //         t.name = ident_case::RenameRule::SnakeCase.apply_to_variant(q.ident.to_string());
-    //     }
-    // }
-    //
+    let mut q = q;
+
+    // common table expressions need unique names
+    for with in q.withs.iter_mut() {
+        with.name = with.name.trim().to_string();
+        with.columns = with.columns.trim().to_string();
+    }
+    for (i, with) in q.withs.iter().enumerate() {
+        if with.name.is_empty() {
+            abort!(
+                q.ident,
+                Error::InvalidWith("name cannot be empty".to_string())
+            );
+        }
+        if q.withs[..i].iter().any(|w| w.name == with.name) {
+            abort!(
+                q.ident,
+                Error::InvalidWith(format!("duplicate name `{}`", with.name))
+            );
+        }
+    }
+
+    // flag is kind of soft delete column
+    if q.soft_delete_flag && q.get_soft_delete().is_none() {
+        abort!(
+            q.ident,
+            r#"Please provide `#[buildix(soft_delete=...)]` column of `soft_delete_flag`"#
+        );
+    }
+
+    // join conditions need to be valid expressions
+    for from in &q.froms {
+        if let FromAttribute::Join { join_on, .. } = from {
+            if join_on.trim().is_empty() {
+                continue;
+            }
+            if let Err(err) = crate::sql::check_expr(join_on) {
+                abort!(join_on.span(), Error::InvalidSql(err));
+            }
+        }
+    }
+
+    let q = validate_group(q);
+
+    let q = validate_table(q);
+
+    validate_schema(&q);
+
+    q
+}
+
+// validate_schema checks tables and columns against schema (when provided). Common table
+// expressions are not part of schema, so their columns are not checked.
+fn validate_schema(q: &Select) {
+    let schema = match crate::schema::Schema::load(&q.schema) {
+        Some(schema) => schema,
+        None => return,
+    };
+
+    // tables by name and alias (None for common table expressions)
+    let mut tables: Vec<(String, Option<&crate::schema::Table>)> = vec![];
+    for from in &q.froms {
+        let (name, alias) = match from {
+            FromAttribute::Table { name, alias } => (name.trim(), alias.trim()),
+            FromAttribute::Join { name, alias, .. } => (name.trim(), alias.trim()),
+        };
+
+        let table = if q.withs.iter().any(|w| w.name == name) {
+            None
+        } else {
+            match schema.table(name) {
+                Some(table) => Some(table),
+                None => abort!(
+                    q.ident,
+                    Error::InvalidSchema(format!("unknown table `{}`", name))
+                ),
+            }
+        };
+
+        tables.push((name.to_string(), table));
+        if !alias.is_empty() {
+            tables.push((alias.to_string(), table));
+        }
+    }
+
+    for field in q.get_fields() {
+        // expressions are not checked (aggregates are checked by column)
+        if field.flatten || (field.aggregate.is_empty() && !field.expr.is_empty()) {
+            continue;
+        }
+        if field.column == "*" {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().unwrap();
+        let nullable = if field.table.is_empty() {
+            // unqualified column has to be in one of tables (unless common table expression is used)
+            let mut found = tables
+                .iter()
+                .filter_map(|(_, t)| t.and_then(|t| t.column(&field.column)));
+            match found.next() {
+                Some(nullable) => Some(nullable),
+                None if tables.iter().any(|(_, t)| t.is_none()) => None,
+                None => abort!(
+                    ident,
+                    Error::InvalidSchema(format!("unknown column `{}`", field.column))
+                ),
+            }
+        } else {
+            match tables.iter().find(|(name, _)| *name == field.table) {
+                Some((_, None)) => None,
+                Some((_, Some(table))) => match table.column(&field.column) {
+                    Some(nullable) => Some(nullable),
+                    None => abort!(
+                        ident,
+                        Error::InvalidSchema(format!(
+                            "unknown column `{}.{}`",
+                            field.table, field.column
+                        ))
+                    ),
+                },
+                None => abort!(
+                    ident,
+                    Error::InvalidSchema(format!("unknown table `{}`", field.table))
+                ),
+            }
+        };
+
+        if nullable == Some(true) && field.aggregate.is_empty() && !crate::select::is_option(&field.ty) {
+            abort!(
+                ident,
+                Error::InvalidSchema(format!(
+                    "column `{}` is nullable, please use `Option<T>`",
+                    field.column
+                ))
+            );
+        }
+    }
+}
+
+// validate_group resolves group entries that reference select fields into their expressions.
+// When select has aggregate fields, every other field has to be grouped.
+fn validate_group(q: Select) -> Select {
+    let mut q = q;
+
+    let fields = q.get_fields();
+    let mut group: Vec<SpannedValue<String>> = Vec::with_capacity(q.group.len());
+    for spanned in &q.group {
+        let entry = spanned.trim();
+        if entry.is_empty() {
+            abort!(
+                q.ident,
+                Error::InvalidGroup("entry cannot be empty".to_string())
+            );
+        }
+
+        match fields
+            .iter()
+            .find(|f| f.ident.as_ref().unwrap() == entry)
+        {
+            Some(field) if !field.aggregate.is_empty() || field.flatten => abort!(
+                field.ident.as_ref().unwrap(),
+                Error::InvalidGroup(format!(
+                    "cannot group by aggregate or flattened field `{}`",
+                    entry
+                ))
+            ),
+            Some(field) => group.push(SpannedValue::new(field.group_expr(), spanned.span())),
+            None => {
+                if let Err(err) = crate::sql::check_expr(entry) {
+                    abort!(spanned.span(), Error::InvalidSql(err));
+                }
+                group.push(SpannedValue::new(entry.to_string(), spanned.span()))
+            }
+        }
+    }
+
+    if fields.iter().any(|f| !f.aggregate.is_empty()) {
+        for field in &fields {
+            if field.flatten {
+                abort!(
+                    field.ident.as_ref().unwrap(),
+                    Error::InvalidGroup(
+                        "flattened fields cannot be used in select with aggregates".to_string()
+                    )
+                );
+            }
+            if field.aggregate.is_empty() && !group.iter().any(|g| **g == field.group_expr()) {
+                abort!(
+                    field.ident.as_ref().unwrap(),
+                    Error::InvalidGroup(format!(
+                        "field `{}` must be aggregated or added to `group`",
+                        field.ident.as_ref().unwrap()
+                    ))
+                );
+            }
+        }
+    }
+
+    q.group = group;
     q
 }
+
+// validate_table infers table name (if not provided) from struct name.
+//  * `from(table(...))` without name uses inferred table name (alias is kept)
+//  * when there is no `from(table(...))`, inferred table is added as first
+fn validate_table(q: Select) -> Select {
+    let mut q = q;
+    q.table = q.table.trim().to_string();
+    q.rename_all = q.rename_all.trim().to_string();
+
+    let has_named_table = q.froms.iter().any(|f| match f {
+        FromAttribute::Table { name, .. } => !name.trim().is_empty(),
+        _ => false,
+    });
+
+    // explicit table cannot be used along with named from(table(...))
+    if !q.table.is_empty() && has_named_table {
+        abort!(
+            q.ident,
+            Error::InvalidTable(
+                "please provide either `table` or `from(table(name = ...))`, but not both"
+                    .to_string()
+            )
+        );
+    }
+
+    if !q.rename_all.is_empty() && (has_named_table || !q.table.is_empty()) {
+        abort!(
+            q.ident,
+            Error::InvalidTable(
+                "`rename_all` is only used when table name is inferred".to_string()
+            )
+        );
+    }
+
+    let table = if !q.table.is_empty() {
+        q.table.clone()
+    } else {
+        infer_table(&q)
+    };
+
+    let mut has_table = false;
+    for from in q.froms.iter_mut() {
+        if let FromAttribute::Table { name, .. } = from {
+            has_table = true;
+            if name.trim().is_empty() {
+                *name = table.clone();
+            }
+        }
+    }
+
+    if !has_table {
+        q.froms.insert(
+            0,
+            FromAttribute::Table {
+                name: table,
+                alias: "".to_string(),
+            },
+        );
+    }
+
+    q
+}
+
+// infer_table returns table name from struct name by `rename_all` rule
+fn infer_table(q: &Select) -> String {
+    let rule = match q.rename_all.as_str() {
+        "" => ident_case::RenameRule::SnakeCase,
+        "none" => ident_case::RenameRule::None,
+        "kebab-case" => abort!(
+            q.ident,
+            Error::InvalidTable("`kebab-case` cannot be used for table name".to_string())
+        ),
+        other => match other.parse() {
+            Ok(rule) => rule,
+            Err(_) => abort!(
+                q.ident,
+                Error::InvalidTable(format!(
+                    "unknown `rename_all` rule `{}`, please provide one of: none, lowercase, \
+                     PascalCase, camelCase, snake_case, SCREAMING_SNAKE_CASE",
+                    other
+                ))
+            ),
+        },
+    };
+
+    let ident = q.ident.to_string();
+    let mut ident = ident.trim_start_matches("r#");
+
+    // conventional `Select` prefix of row type is not part of table name (`SelectUser` selects
+    // from `user`, `Selection` from `selection`)
+    if let Some(rest) = ident.strip_prefix("Select") {
+        if rest.starts_with(|c: char| c.is_ascii_uppercase()) {
+            ident = rest;
+        }
+    }
+    let table = rule.apply_to_variant(ident);
+
+    if table.is_empty() {
+        abort!(
+            q.ident,
+            Error::InvalidTable(
+                "cannot infer table name, please provide `#[buildix(table = ...)]`".to_string()
+            )
+        );
+    }
+
+    table
+}
@@ -0,0 +1,109 @@
+// sql checks syntax of sql fragments provided in attributes (expressions, join conditions,
+// group entries) at compile time. Fragments are parsed by every enabled database dialect,
+// generic dialect is used when no database feature is enabled.
+
+use sqlparser::dialect::{Dialect, GenericDialect};
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+// dialects returns parsers dialects for enabled database features
+fn dialects() -> Vec<(&'static str, Box<dyn Dialect>)> {
+    let mut dialects: Vec<(&'static str, Box<dyn Dialect>)> = vec![];
+
+    if cfg!(feature = "postgres") {
+        dialects.push((
+            "postgres",
+            Box::new(sqlparser::dialect::PostgreSqlDialect {}),
+        ));
+    }
+    if cfg!(feature = "mysql") {
+        dialects.push(("mysql", Box::new(sqlparser::dialect::MySqlDialect {})));
+    }
+    if cfg!(feature = "sqlite") {
+        dialects.push(("sqlite", Box::new(sqlparser::dialect::SQLiteDialect {})));
+    }
+    if cfg!(feature = "mssql") {
+        dialects.push(("mssql", Box::new(sqlparser::dialect::MsSqlDialect {})));
+    }
+    if dialects.is_empty() {
+        dialects.push(("generic", Box::new(GenericDialect {})));
+    }
+
+    dialects
+}
+
+// dialect returns dialect of first enabled database feature (used for schema files)
+pub fn dialect() -> Box<dyn Dialect> {
+    dialects().remove(0).1
+}
+
+// tokenize returns tokens of fragment (by generic dialect, which reads `?` as placeholder)
+fn tokenize(sql: &str) -> Result<Vec<Token>, String> {
+    Tokenizer::new(&GenericDialect {}, sql)
+        .tokenize()
+        .map_err(|err| format!("invalid sql `{}`: {}", sql, err))
+}
+
+// numbered returns fragment with `$n` placeholders instead of `?` (postgres reads `?` as operator)
+fn numbered(sql: &str) -> Result<String, String> {
+    let tokens = Tokenizer::new(&GenericDialect {}, sql)
+        .tokenize_with_location()
+        .map_err(|err| format!("invalid sql `{}`: {}", sql, err))?;
+
+    // placeholder positions (line and column) in fragment
+    let positions: Vec<(u64, u64)> = tokens
+        .iter()
+        .filter(|t| matches!(&t.token, Token::Placeholder(p) if p == "?"))
+        .map(|t| (t.span.start.line, t.span.start.column))
+        .collect();
+
+    let mut result = String::with_capacity(sql.len());
+    let mut counter = 0;
+    for (line, text) in sql.split('\n').enumerate() {
+        if line > 0 {
+            result.push('\n');
+        }
+        for (column, ch) in text.chars().enumerate() {
+            if positions.contains(&(line as u64 + 1, column as u64 + 1)) {
+                counter += 1;
+                result.push_str(&format!("${}", counter));
+            } else {
+                result.push(ch);
+            }
+        }
+    }
+    Ok(result)
+}
+
+// check_expr checks that fragment is single valid sql expression
+pub fn check_expr(sql: &str) -> Result<(), String> {
+    for (name, dialect) in dialects() {
+        let fragment = if name == "postgres" {
+            numbered(sql)?
+        } else {
+            sql.to_string()
+        };
+
+        let result = Parser::new(dialect.as_ref())
+            .try_with_sql(&fragment)
+            .and_then(|mut parser| {
+                parser.parse_expr()?;
+                parser.expect_token(&Token::EOF)?;
+                Ok(())
+            });
+
+        if let Err(err) = result {
+            return Err(format!("invalid sql `{}` ({}): {}", sql, name, err));
+        }
+    }
+    Ok(())
+}
+
+// placeholders returns count of `?` placeholders in fragment (placeholders in string literals
+// are not counted)
+pub fn placeholders(sql: &str) -> Result<usize, String> {
+    Ok(tokenize(sql)?
+        .iter()
+        .filter(|t| matches!(t, Token::Placeholder(p) if p == "?"))
+        .count())
+}